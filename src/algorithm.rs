@@ -3,14 +3,139 @@
 /// Singing algorithms supported by this implementation
 ///
 /// Not all algorithms are supported by all serialization formats. JWT does not support ES512; COSE
-/// does not support PS256, PS384, and PS512.
-#[derive(Debug)]
+/// does not support PS256, PS384, PS512, RS256, RS384, and RS512.
+///
+/// ES256K (ECDSA using secp256k1) is not one of the algorithms commonly used for EARs, and is only
+/// supported when the `es256k` feature is enabled (though the variant itself is always present,
+/// so that enabling the feature does not change the enum).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum Algorithm {
     PS256,
     PS384,
     PS512,
+    /// RSASSA-PKCS1-v1_5 using SHA-256 (JWT only)
+    RS256,
+    /// RSASSA-PKCS1-v1_5 using SHA-384 (JWT only)
+    RS384,
+    /// RSASSA-PKCS1-v1_5 using SHA-512 (JWT only)
+    RS512,
     ES256,
     ES384,
     ES512,
     EdDSA,
+    /// ECDSA using secp256k1 and SHA-256 (see RFC 8812)
+    ///
+    /// This is only supported when the `es256k` feature is enabled; otherwise, it is rejected as
+    /// not supported.
+    ES256K,
+}
+
+impl Algorithm {
+    /// Return the algorithms that may be used to sign and verify JWT-encoded EARs
+    pub fn supported_jwt() -> &'static [Algorithm] {
+        // NOTE: must be kept in sync with alg_to_jwt_alg() and jwt_alg_from_name() in ear.rs
+        // (ES256K, which jsonwebtoken does not support, is handled by es256k.rs)
+        &[
+            Algorithm::PS256,
+            Algorithm::PS384,
+            Algorithm::PS512,
+            Algorithm::RS256,
+            Algorithm::RS384,
+            Algorithm::RS512,
+            Algorithm::ES256,
+            Algorithm::ES384,
+            Algorithm::EdDSA,
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K,
+        ]
+    }
+
+    /// Return the algorithms that may be used to sign and verify COSE-encoded EARs
+    pub fn supported_cose() -> &'static [Algorithm] {
+        // NOTE: must be kept in sync with alg_to_cose() in ear.rs
+        &[
+            Algorithm::ES256,
+            Algorithm::ES384,
+            Algorithm::ES512,
+            Algorithm::EdDSA,
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K,
+        ]
+    }
+}
+
+/// MAC algorithms supported by this implementation (for MACed EARs, see the
+/// [crate documentation](crate#maced-ears))
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MacAlgorithm {
+    /// HMAC w/ SHA-256 (256-bit tag)
+    HS256,
+    /// HMAC w/ SHA-384 (384-bit tag)
+    HS384,
+    /// HMAC w/ SHA-512 (512-bit tag)
+    HS512,
+}
+
+/// Digest algorithms used to compute content identifiers (see
+/// [`Ear::content_id`](crate::Ear::content_id)) and to check evidence digests (see
+/// [`Appraisal::verify_evidence_digests`](crate::Appraisal::verify_evidence_digests))
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DigestAlg {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl DigestAlg {
+    /// Return the digest of the specified data
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            DigestAlg::Sha256 => openssl::sha::sha256(data).to_vec(),
+            DigestAlg::Sha384 => openssl::sha::sha384(data).to_vec(),
+            DigestAlg::Sha512 => openssl::sha::sha512(data).to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ear::{alg_to_cose, alg_to_jwt_alg};
+
+    const ALL: &[Algorithm] = &[
+        Algorithm::PS256,
+        Algorithm::PS384,
+        Algorithm::PS512,
+        Algorithm::RS256,
+        Algorithm::RS384,
+        Algorithm::RS512,
+        Algorithm::ES256,
+        Algorithm::ES384,
+        Algorithm::ES512,
+        Algorithm::EdDSA,
+    ];
+
+    #[test]
+    fn supported() {
+        assert!(Algorithm::supported_jwt().contains(&Algorithm::ES256));
+        assert!(Algorithm::supported_cose().contains(&Algorithm::ES256));
+        assert!(Algorithm::supported_cose().contains(&Algorithm::ES512));
+        assert!(!Algorithm::supported_jwt().contains(&Algorithm::ES512));
+        assert!(Algorithm::supported_jwt().contains(&Algorithm::RS256));
+        assert!(!Algorithm::supported_cose().contains(&Algorithm::RS256));
+
+        for alg in ALL {
+            assert_eq!(
+                Algorithm::supported_jwt().contains(alg),
+                alg_to_jwt_alg(alg).is_ok(),
+                "{alg:?}"
+            );
+            assert_eq!(
+                Algorithm::supported_cose().contains(alg),
+                alg_to_cose(alg).is_ok(),
+                "{alg:?}"
+            );
+        }
+    }
 }
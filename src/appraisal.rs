@@ -1,16 +1,30 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::BTreeMap, fmt};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use serde::{
-    de::{Deserialize, Visitor},
+    de::{self, Deserialize, Visitor},
     ser::{Serialize, SerializeMap},
 };
 
-use crate::{get_profile, Error, Extensions, KeyAttestation, RawValue, TrustTier, TrustVector};
+use crate::debug::LenientDebugStatus;
+use crate::trust::tier::LenientTrustTier;
+use crate::trust::vector::LenientTrustVector;
+use crate::{
+    get_profile, Bytes, DebugStatus, DigestAlg, Error, Extensions, KeyAttestation, MapKey,
+    RawValue, RawValueKind, TrustTier, TrustVector,
+};
 
 /// An appraisal crated by a verifier of the evidence provided by an attester
-#[derive(Debug, PartialEq)]
+///
+/// The `Debug` output summarizes the annotated evidence and the nested EAR (e.g.
+/// `annotated_evidence: <3 claims>`) rather than including them in full; use
+/// [`Appraisal::debug_verbose`] for the full details.
+#[derive(PartialEq)]
 pub struct Appraisal {
     /// The overall status of the appraisal represented by an AR4SI trustworthiness tier
     ///
@@ -24,15 +38,30 @@ pub struct Appraisal {
     /// Evidence claims extracted and annotated by the verifier from the evidence supplied by the
     /// attester
     /// (note: this is a Veraison project extension to EAR)
-    pub annotated_evidence: BTreeMap<String, RawValue>,
+    pub annotated_evidence: BTreeMap<MapKey, RawValue>,
     /// Addition claims made as part of the appraisal based on the policy indicated by `policy_id`
     /// (note: this is a Veraison project extension to EAR)
-    pub policy_claims: BTreeMap<String, RawValue>,
+    pub policy_claims: BTreeMap<MapKey, RawValue>,
     /// Claims about the public key that is being attested
     /// (note: this is a Veraison project extension to EAR)
     pub key_attestation: Option<KeyAttestation>,
+    /// A signed EAR (JWT or COSE token) produced by the sub-verifier that appraised this submod,
+    /// in composite attestation (see [`Ear::verify_nested_submods`](crate::Ear::verify_nested_submods))
+    /// (note: this is a Veraison project extension to EAR)
+    pub nested_ear: Option<Bytes>,
+    /// The time at which this submod was appraised, in seconds since the UNIX epoch (this may
+    /// differ from the `iat` of the EAR containing the appraisal)
+    /// (note: this is a Veraison project extension to EAR)
+    pub appraisal_time: Option<i64>,
+    /// The debug status of the attested environment (the EAT `dbgstat` claim); see
+    /// [`DebugStatus::suggested_tier`] for how this may inform the appraisal
+    pub debug_status: Option<DebugStatus>,
     /// extension claims
     pub extensions: Extensions,
+    // serialization option (see Appraisal::set_emit_empty_trust_vector)
+    emit_empty_trust_vector: Ignored<bool>,
+    // problems found when decoding that were not severe enough to reject the appraisal
+    warnings: Ignored<Vec<String>>,
 }
 
 impl Appraisal {
@@ -45,7 +74,12 @@ impl Appraisal {
             annotated_evidence: BTreeMap::new(),
             policy_claims: BTreeMap::new(),
             key_attestation: None,
+            nested_ear: None,
+            appraisal_time: None,
+            debug_status: None,
             extensions: Extensions::new(),
+            emit_empty_trust_vector: Ignored(false),
+            warnings: Ignored(Vec::new()),
         }
     }
 
@@ -58,7 +92,12 @@ impl Appraisal {
             annotated_evidence: BTreeMap::new(),
             policy_claims: BTreeMap::new(),
             key_attestation: None,
+            nested_ear: None,
+            appraisal_time: None,
+            debug_status: None,
             extensions: Extensions::new(),
+            emit_empty_trust_vector: Ignored(false),
+            warnings: Ignored(Vec::new()),
         };
 
         match get_profile(profile) {
@@ -70,15 +109,383 @@ impl Appraisal {
         }
     }
 
+    /// Return a value whose `Debug` output includes the full annotated evidence and nested EAR
+    /// (see the note on [`Appraisal`])
+    pub fn debug_verbose(&self) -> impl fmt::Debug + '_ {
+        AppraisalDebug {
+            appraisal: self,
+            verbose: true,
+        }
+    }
+
+    /// Return an iterator over the annotated evidence claims, as (key, value) pairs
+    pub fn evidence_iter(&self) -> impl Iterator<Item = (&MapKey, &RawValue)> {
+        self.annotated_evidence.iter()
+    }
+
+    /// Return the number of annotated evidence claims
+    pub fn annotated_evidence_len(&self) -> usize {
+        self.annotated_evidence.len()
+    }
+
+    /// Return the annotated evidence claim with the specified key, if it is present and is a
+    /// string
+    pub fn evidence_str(&self, key: impl Into<MapKey>) -> Option<&str> {
+        self.annotated_evidence.get(&key.into())?.as_str()
+    }
+
+    /// Return the annotated evidence claim with the specified key, if it is present and is a byte
+    /// string
+    ///
+    /// As byte strings are base64-encoded in JSON, a string claim will be decoded into bytes (if it
+    /// is valid base64).
+    pub fn evidence_bytes(&self, key: impl Into<MapKey>) -> Option<Vec<u8>> {
+        raw_bytes(self.annotated_evidence.get(&key.into())?)
+    }
+
+    /// Return the annotated evidence claim with the specified key, if it is present and is an
+    /// integer
+    pub fn evidence_i64(&self, key: impl Into<MapKey>) -> Option<i64> {
+        self.annotated_evidence.get(&key.into())?.as_i64()
+    }
+
+    /// Set the policy claim with the specified key, replacing any existing value
+    pub fn set_policy_claim(&mut self, key: impl Into<MapKey>, value: impl Into<RawValue>) {
+        self.policy_claims.insert(key.into(), value.into());
+    }
+
+    /// Return the policy claim with the specified key, if it is present
+    pub fn policy_claim(&self, key: impl Into<MapKey>) -> Option<&RawValue> {
+        self.policy_claims.get(&key.into())
+    }
+
+    /// Return the policy claim with the specified key, if it is present and is a string
+    pub fn policy_claim_str(&self, key: impl Into<MapKey>) -> Option<&str> {
+        self.policy_claim(key)?.as_str()
+    }
+
+    /// Return the policy claim with the specified key, if it is present and is an integer
+    pub fn policy_claim_i64(&self, key: impl Into<MapKey>) -> Option<i64> {
+        self.policy_claim(key)?.as_i64()
+    }
+
+    /// Return the policy claim with the specified key, if it is present and is a boolean
+    pub fn policy_claim_bool(&self, key: impl Into<MapKey>) -> Option<bool> {
+        self.policy_claim(key)?.as_bool()
+    }
+
+    // Return every problem with the appraisal that makes the EAR containing it invalid (see
+    // Ear::validate_all(), which prefixes them with the name of the submod).
+    pub(crate) fn validate_all(&self) -> Result<(), Vec<Error>> {
+        let mut errs: Vec<Error> = Vec::new();
+
+        if let Some(ka) = &self.key_attestation {
+            if ka.pub_key.as_slice().is_empty() {
+                errs.push(Error::ValidationError(
+                    "key attestation present but akpub empty".to_string(),
+                ));
+            }
+        }
+
+        if let Some(nested) = &self.nested_ear {
+            if nested.as_slice().is_empty() {
+                errs.push(Error::ValidationError(
+                    "nested EAR present but empty".to_string(),
+                ));
+            }
+        }
+
+        if errs.is_empty() {
+            Ok(())
+        } else {
+            Err(errs)
+        }
+    }
+
+    /// Ensure that the `status` is consistent with the claims in the trustworthiness vector
+    ///
+    /// The status is inconsistent if it is lower than the tier of any claim that has been set
+    /// (e.g. the status is affirming, but one of the claims is contraindicated). Unlike
+    /// `update_status_from_trust_vector`, this does not modify the appraisal.
+    pub fn check_status_consistency(&self) -> Result<(), Error> {
+        for claim in &self.trust_vector {
+            if !claim.is_set() {
+                continue;
+            }
+
+            let claim_tier = claim.tier();
+            if self.status < claim_tier {
+                return Err(Error::ValidationError(format!(
+                    "status {status:?} is lower than {claim_tier:?} tier of {tag} claim ({name})",
+                    status = self.status,
+                    tag = claim.tag(),
+                    name = claim.value_name(),
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ensure that the annotated evidence claims match the digests of them declared in the policy
+    /// claims
+    ///
+    /// A policy claim declares a digest if its key is the key of an annotated evidence claim with
+    /// the name of a digest algorithm appended, i.e. `<evidence-key>.sha-256`,
+    /// `<evidence-key>.sha-384`, or `<evidence-key>.sha-512`, and its value is the digest of the
+    /// evidence claim. Both the digest and the evidence claim must be byte strings (which are
+    /// base64-encoded strings in JSON). Policy claims with any other keys are ignored.
+    pub fn verify_evidence_digests(&self) -> Result<(), Error> {
+        for (key, value) in &self.policy_claims {
+            let Some((evidence_key, alg)) = key.as_str().and_then(split_digest_key) else {
+                continue;
+            };
+
+            let expected = raw_bytes(value).ok_or_else(|| {
+                Error::ValidationError(format!("policy claim {key}: digest is not a byte string"))
+            })?;
+
+            if !self
+                .annotated_evidence
+                .contains_key(&MapKey::from(evidence_key))
+            {
+                return Err(Error::ValidationError(format!(
+                    "policy claim {key}: no annotated evidence claim {evidence_key}"
+                )));
+            }
+
+            let evidence = self.evidence_bytes(evidence_key).ok_or_else(|| {
+                Error::ValidationError(format!(
+                    "policy claim {key}: annotated evidence claim {evidence_key} is not a byte string"
+                ))
+            })?;
+
+            if alg.digest(&evidence) != expected {
+                return Err(Error::ValidationError(format!(
+                    "policy claim {key}: digest does not match annotated evidence claim {evidence_key}"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the `appraisal_time` as a `SystemTime`, if it is set
+    pub fn appraisal_system_time(&self) -> Option<SystemTime> {
+        let secs = self.appraisal_time?;
+        let offset = Duration::from_secs(secs.unsigned_abs());
+
+        if secs < 0 {
+            UNIX_EPOCH.checked_sub(offset)
+        } else {
+            UNIX_EPOCH.checked_add(offset)
+        }
+    }
+
+    /// Set the `appraisal_time` from a `SystemTime` (truncating it to whole seconds)
+    pub fn set_appraisal_system_time(&mut self, time: SystemTime) {
+        self.appraisal_time = Some(match time.duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs() as i64),
+        });
+    }
+
+    /// Set whether the trustworthiness vector is always emitted when the appraisal is serialized,
+    /// even if none of its claims have been set (in which case it is serialized as an empty map)
+    ///
+    /// By default, an empty vector is omitted. This is a serialization option, rather than part
+    /// of the appraisal, and so is ignored when comparing appraisals.
+    pub fn set_emit_empty_trust_vector(&mut self, emit: bool) {
+        self.emit_empty_trust_vector.0 = emit;
+    }
+
+    /// Return the warnings recorded when this appraisal was decoded
+    ///
+    /// Decoding is lenient: an appraisal whose `status` is inconsistent with its trustworthiness
+    /// vector (see [`Appraisal::check_status_consistency`]) is accepted, and the inconsistency is
+    /// recorded here. Callers that want strict decoding should call
+    /// `check_status_consistency` on the decoded appraisal, which will fail instead.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings.0
+    }
+
     /// Set the `status` based on the theirs of the claims in the trustworthiness vector
     pub fn update_status_from_trust_vector(&mut self) {
-        for claim in self.trust_vector {
+        for claim in &self.trust_vector {
             let claim_tier = claim.tier();
             if self.status < claim_tier {
                 self.status = claim_tier
             }
         }
     }
+
+    /// Return an iterator over the claims in the trustworthiness vector that have been set, in key
+    /// order, as (name, value, tier) tuples
+    pub fn set_claims(&self) -> impl Iterator<Item = (&str, i8, TrustTier)> {
+        self.trust_vector
+            .iter()
+            .filter(|claim| claim.is_set())
+            .map(|claim| (claim.tag(), claim.value(), claim.tier()))
+    }
+
+    /// Return the name and value of the claim in the trustworthiness vector that determines the
+    /// status (see [`Appraisal::update_status_from_trust_vector`]), i.e. the set claim with the
+    /// worst tier
+    ///
+    /// If several claims share the worst tier, the one with the lowest key is returned. `None` is
+    /// returned if no claims are set.
+    pub fn status_driver(&self) -> Option<(&str, i8)> {
+        let mut driver: Option<(&str, i8, TrustTier)> = None;
+
+        for (name, value, tier) in self.set_claims() {
+            let worse = match &driver {
+                Some((_, _, t)) => tier > *t,
+                None => true,
+            };
+
+            if worse {
+                driver = Some((name, value, tier));
+            }
+        }
+
+        driver.map(|(name, value, _)| (name, value))
+    }
+}
+
+// Return the value as bytes, if it is a byte string, or a base64-encoded string (as byte strings
+// are in JSON).
+fn raw_bytes(value: &RawValue) -> Option<Vec<u8>> {
+    match value.as_bytes() {
+        Some(b) => Some(b.to_vec()),
+        None => value
+            .convert(&RawValueKind::Bytes)
+            .ok()?
+            .as_bytes()
+            .map(|b| b.to_vec()),
+    }
+}
+
+// Return the key of the trustworthiness claim identified by an appraisal root key, if any.
+//
+// Some producers place the trustworthiness vector inline at the appraisal root (rather than within
+// ear.trustworthiness-vector), with each claim identified by its name (or, in CBOR, by its key),
+// and its value being the tier of the claim, e.g.
+//   {"ear.status": "warning", "hardware": "affirming", "executables": "warning"}
+// This is only accepted by lenient decoding (see Ear::from_json_lenient).
+fn inline_claim_key(key: &MapKey) -> Option<i32> {
+    let tv = TrustVector::new();
+
+    let claim = match key {
+        MapKey::Text(name) => tv.by_name(name).ok()?,
+        MapKey::Integer(key) => tv.by_key(i32::try_from(*key).ok()?).ok()?,
+    };
+
+    Some(i32::from(claim.key()))
+}
+
+// Split the key of a policy claim declaring an evidence digest (see
+// Appraisal::verify_evidence_digests) into the evidence claim key and the digest algorithm.
+fn split_digest_key(key: &str) -> Option<(&str, DigestAlg)> {
+    let (evidence_key, alg) = key.rsplit_once('.')?;
+
+    let alg = match alg {
+        "sha-256" => DigestAlg::Sha256,
+        "sha-384" => DigestAlg::Sha384,
+        "sha-512" => DigestAlg::Sha512,
+        _ => return None,
+    };
+
+    Some((evidence_key, alg))
+}
+
+impl fmt::Debug for Appraisal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        AppraisalDebug {
+            appraisal: self,
+            verbose: false,
+        }
+        .fmt(f)
+    }
+}
+
+// Formats an appraisal for Debug, summarizing the (potentially large, or sensitive) evidence
+// unless verbose.
+pub(crate) struct AppraisalDebug<'a> {
+    pub appraisal: &'a Appraisal,
+    pub verbose: bool,
+}
+
+impl fmt::Debug for AppraisalDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // destructured exhaustively, so that a new field cannot be left out by mistake
+        let Appraisal {
+            status,
+            trust_vector,
+            policy_id,
+            annotated_evidence,
+            policy_claims,
+            key_attestation,
+            nested_ear,
+            appraisal_time,
+            debug_status,
+            extensions,
+            emit_empty_trust_vector,
+            warnings,
+        } = self.appraisal;
+
+        let mut d = f.debug_struct("Appraisal");
+
+        d.field("status", status)
+            .field("trust_vector", trust_vector)
+            .field("policy_id", policy_id);
+
+        if self.verbose {
+            d.field("annotated_evidence", annotated_evidence);
+        } else {
+            d.field(
+                "annotated_evidence",
+                &Summary(format!("<{} claims>", annotated_evidence.len())),
+            );
+        }
+
+        d.field("policy_claims", policy_claims)
+            .field("key_attestation", key_attestation);
+
+        match nested_ear {
+            Some(b) if !self.verbose => d.field(
+                "nested_ear",
+                &Some(Summary(format!("<{} bytes>", b.as_slice().len()))),
+            ),
+            _ => d.field("nested_ear", nested_ear),
+        };
+
+        d.field("appraisal_time", appraisal_time)
+            .field("debug_status", debug_status)
+            .field("extensions", extensions)
+            .field("emit_empty_trust_vector", &emit_empty_trust_vector.0)
+            .field("warnings", &warnings.0)
+            .finish()
+    }
+}
+
+// A string that is Debug-formatted without quotes
+pub(crate) struct Summary(pub String);
+
+impl fmt::Debug for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// A value that is not part of an appraisal's contents, and so is ignored when comparing
+// appraisals: e.g. the warnings are an artifact of how the appraisal was decoded, and
+// emit_empty_trust_vector of how it is to be encoded.
+struct Ignored<T>(T);
+
+impl<T> PartialEq for Ignored<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
 impl Default for Appraisal {
@@ -98,7 +505,7 @@ impl Serialize for Appraisal {
         if is_human_readable {
             map.serialize_entry("ear.status", &self.status)?;
 
-            if self.trust_vector.any_set() {
+            if self.trust_vector.any_set() || self.emit_empty_trust_vector.0 {
                 map.serialize_entry("ear.trustworthiness-vector", &self.trust_vector)?;
             }
 
@@ -114,12 +521,24 @@ impl Serialize for Appraisal {
                 map.serialize_entry("ear.veraison.policy-claims", &self.policy_claims)?;
             }
 
+            if let Some(nested) = &self.nested_ear {
+                map.serialize_entry("ear.veraison.nested-ear", nested)?;
+            }
+
+            if let Some(t) = self.appraisal_time {
+                map.serialize_entry("ear.veraison.appraisal-time", &t)?;
+            }
+
+            if let Some(d) = &self.debug_status {
+                map.serialize_entry("dbgstat", d)?;
+            }
+
             self.extensions.serialize_to_map_by_name(&mut map)?;
         } else {
             // !is_human_readable
             map.serialize_entry(&1000, &self.status)?;
 
-            if self.trust_vector.any_set() {
+            if self.trust_vector.any_set() || self.emit_empty_trust_vector.0 {
                 map.serialize_entry(&1001, &self.trust_vector)?;
             }
 
@@ -135,6 +554,18 @@ impl Serialize for Appraisal {
                 map.serialize_entry(&-70001, &self.policy_claims)?;
             }
 
+            if let Some(nested) = &self.nested_ear {
+                map.serialize_entry(&-70003, nested)?;
+            }
+
+            if let Some(t) = self.appraisal_time {
+                map.serialize_entry(&-70004, &t)?;
+            }
+
+            if let Some(d) = &self.debug_status {
+                map.serialize_entry(&263, d)?;
+            }
+
             self.extensions.serialize_to_map_by_key(&mut map)?;
         }
 
@@ -151,12 +582,52 @@ impl<'de> Deserialize<'de> for Appraisal {
 
         deserializer.deserialize_map(AppraisalVisitor {
             is_human_readable: is_hr,
+            lenient: false,
         })
     }
 }
 
-struct AppraisalVisitor {
+pub(crate) struct AppraisalVisitor {
     pub is_human_readable: bool,
+    // accept a boolean status (see LenientTrustTier), a trustworthiness vector mixing claim names
+    // and keys (see LenientTrustVector), a named debug status (see LenientDebugStatus), and
+    // trustworthiness claims inline at the appraisal root (see inline_claim_key)
+    pub lenient: bool,
+}
+
+impl AppraisalVisitor {
+    fn next_status<'de, A>(&self, map: &mut A) -> Result<TrustTier, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        if self.lenient {
+            Ok(map.next_value::<LenientTrustTier>()?.0)
+        } else {
+            map.next_value::<TrustTier>()
+        }
+    }
+
+    fn next_debug_status<'de, A>(&self, map: &mut A) -> Result<DebugStatus, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        if self.lenient {
+            Ok(map.next_value::<LenientDebugStatus>()?.0)
+        } else {
+            map.next_value::<DebugStatus>()
+        }
+    }
+
+    fn next_trust_vector<'de, A>(&self, map: &mut A) -> Result<TrustVector, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        if self.lenient {
+            Ok(map.next_value::<LenientTrustVector>()?.0)
+        } else {
+            map.next_value::<TrustVector>()
+        }
+    }
 }
 
 impl<'de> Visitor<'de> for AppraisalVisitor {
@@ -171,27 +642,44 @@ impl<'de> Visitor<'de> for AppraisalVisitor {
         A: serde::de::MapAccess<'de>,
     {
         let mut appraisal = Appraisal::new();
+        // trustworthiness claims found inline at the appraisal root, as (claim key, tier)
+        let mut inline_claims: Vec<(i32, TrustTier)> = Vec::new();
 
         loop {
             if self.is_human_readable {
                 match map.next_key::<&str>()? {
-                    Some("ear.status") => appraisal.status = map.next_value::<TrustTier>()?,
+                    Some("ear.status") => appraisal.status = self.next_status(&mut map)?,
                     Some("ear.trustworthiness-vector") => {
-                        appraisal.trust_vector = map.next_value::<TrustVector>()?
+                        appraisal.trust_vector = self.next_trust_vector(&mut map)?
                     }
                     Some("ear.appraisal-policy-id") => {
                         appraisal.policy_id = Some(map.next_value::<String>()?)
                     }
                     Some("ear.veraison.annotated-evidence") => {
                         appraisal.annotated_evidence =
-                            map.next_value::<BTreeMap<String, RawValue>>()?
+                            map.next_value::<BTreeMap<MapKey, RawValue>>()?
                     }
                     Some("ear.veraison.policy-claims") => {
-                        appraisal.policy_claims = map.next_value::<BTreeMap<String, RawValue>>()?
+                        appraisal.policy_claims = map.next_value::<BTreeMap<MapKey, RawValue>>()?
                     }
                     Some("ear.veraison.key-attestation") => {
                         appraisal.key_attestation = Some(map.next_value::<KeyAttestation>()?)
                     }
+                    Some("ear.veraison.nested-ear") => {
+                        appraisal.nested_ear = Some(map.next_value::<Bytes>()?)
+                    }
+                    Some("ear.veraison.appraisal-time") => {
+                        appraisal.appraisal_time = Some(map.next_value::<i64>()?)
+                    }
+                    Some("dbgstat") => {
+                        appraisal.debug_status = Some(self.next_debug_status(&mut map)?)
+                    }
+                    Some(name) if self.lenient => match inline_claim_key(&MapKey::from(name)) {
+                        Some(key) => inline_claims.push((key, map.next_value::<TrustTier>()?)),
+                        None => appraisal
+                            .extensions
+                            .visit_map_entry_by_name(name, &mut map)?,
+                    },
                     Some(name) => appraisal
                         .extensions
                         .visit_map_entry_by_name(name, &mut map)?,
@@ -199,33 +687,57 @@ impl<'de> Visitor<'de> for AppraisalVisitor {
                 }
             } else {
                 // !is_human_readable
-                match map.next_key::<i32>()? {
-                    Some(1000) => appraisal.status = map.next_value::<TrustTier>()?,
-                    Some(1001) => appraisal.trust_vector = map.next_value::<TrustVector>()?,
+                match map.next_key::<i64>()? {
+                    Some(1000) => appraisal.status = self.next_status(&mut map)?,
+                    Some(1001) => appraisal.trust_vector = self.next_trust_vector(&mut map)?,
                     Some(1003) => appraisal.policy_id = Some(map.next_value::<String>()?),
                     Some(-70000) => {
                         appraisal.annotated_evidence =
-                            map.next_value::<BTreeMap<String, RawValue>>()?
+                            map.next_value::<BTreeMap<MapKey, RawValue>>()?
                     }
                     Some(-70001) => {
-                        appraisal.policy_claims = map.next_value::<BTreeMap<String, RawValue>>()?
+                        appraisal.policy_claims = map.next_value::<BTreeMap<MapKey, RawValue>>()?
                     }
                     Some(-70002) => {
                         appraisal.key_attestation = Some(map.next_value::<KeyAttestation>()?)
                     }
+                    Some(-70003) => appraisal.nested_ear = Some(map.next_value::<Bytes>()?),
+                    Some(-70004) => appraisal.appraisal_time = Some(map.next_value::<i64>()?),
+                    Some(263) => appraisal.debug_status = Some(self.next_debug_status(&mut map)?),
+                    Some(key) if self.lenient => match inline_claim_key(&MapKey::Integer(key)) {
+                        Some(key) => inline_claims.push((key, map.next_value::<TrustTier>()?)),
+                        None => appraisal.extensions.visit_map_entry_by_key(key, &mut map)?,
+                    },
                     Some(key) => appraisal.extensions.visit_map_entry_by_key(key, &mut map)?,
                     None => break,
                 }
             }
         }
 
+        // inline claims are applied on top of any ear.trustworthiness-vector
+        for (key, tier) in inline_claims {
+            appraisal
+                .trust_vector
+                .mut_by_key(key)
+                .map_err(de::Error::custom)?
+                .set(tier.value());
+        }
+
+        if let Err(Error::ValidationError(msg)) = appraisal.check_status_consistency() {
+            appraisal.warnings.0.push(msg);
+        }
+
         Ok(appraisal)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{claim, Appraisal};
+    use crate::{
+        claim, Appraisal, Bytes, DebugStatus, DigestAlg, Error, MapKey, RawValue, TrustTier,
+    };
+    use ciborium::{de::from_reader, ser::into_writer};
+    use std::time::{Duration, UNIX_EPOCH};
 
     #[test]
     fn serde() {
@@ -247,4 +759,433 @@ mod test {
         let appraisal2: Appraisal = serde_json::from_str(val.as_str()).unwrap();
         assert_eq!(appraisal, appraisal2);
     }
+
+    #[test]
+    fn emit_empty_trust_vector() {
+        let mut appraisal = Appraisal::new();
+        let val = serde_json::to_string(&appraisal).unwrap();
+        assert_eq!(val, r#"{"ear.status":"none"}"#);
+
+        appraisal.set_emit_empty_trust_vector(true);
+        let val = serde_json::to_string(&appraisal).unwrap();
+        assert_eq!(
+            val,
+            r#"{"ear.status":"none","ear.trustworthiness-vector":{}}"#
+        );
+
+        let appraisal2: Appraisal = serde_json::from_str(val.as_str()).unwrap();
+        assert!(!appraisal2.trust_vector.any_set());
+        // the option is not part of the appraisal
+        assert_eq!(appraisal2, appraisal);
+
+        let mut buf: Vec<u8> = Vec::new();
+        into_writer(&appraisal, &mut buf).unwrap();
+        assert_eq!(
+            buf,
+            vec![
+                0xbf, // map (indefinite length)
+                0x19, 0x03, 0xe8, // 1000
+                0x00, // 0
+                0x19, 0x03, 0xe9, // 1001
+                0xbf, // map (indefinite length)
+                0xff, // break
+                0xff, // break
+            ]
+        );
+
+        let appraisal2: Appraisal = from_reader(buf.as_slice()).unwrap();
+        assert!(!appraisal2.trust_vector.any_set());
+    }
+
+    #[test]
+    fn evidence() {
+        let mut appraisal = Appraisal::new();
+        appraisal.annotated_evidence.insert(
+            "platform".into(),
+            RawValue::String("acme-rr-trap".to_string()),
+        );
+        appraisal.annotated_evidence.insert(
+            "measurement".into(),
+            RawValue::Bytes(Bytes::from([0xde, 0xad, 0xbe, 0xef].as_slice())),
+        );
+        appraisal
+            .annotated_evidence
+            .insert("version".into(), RawValue::Integer(7));
+
+        assert_eq!(appraisal.annotated_evidence_len(), 3);
+        assert_eq!(Appraisal::new().annotated_evidence_len(), 0);
+
+        assert_eq!(appraisal.evidence_str("platform"), Some("acme-rr-trap"));
+        assert_eq!(appraisal.evidence_str("version"), None);
+        assert_eq!(appraisal.evidence_str("missing"), None);
+
+        assert_eq!(
+            appraisal.evidence_bytes("measurement"),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(appraisal.evidence_bytes("version"), None);
+
+        assert_eq!(appraisal.evidence_i64("version"), Some(7));
+        assert_eq!(appraisal.evidence_i64("platform"), None);
+
+        let names: Vec<String> = appraisal
+            .evidence_iter()
+            .map(|(k, _)| k.to_string())
+            .collect();
+        assert_eq!(names, vec!["measurement", "platform", "version"]);
+
+        // byte strings are base64-encoded in JSON, and so are read back as text strings
+        let val = serde_json::to_string(&appraisal).unwrap();
+        let appraisal2: Appraisal = serde_json::from_str(val.as_str()).unwrap();
+        assert_eq!(
+            appraisal2.evidence_bytes("measurement"),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+    }
+
+    #[test]
+    fn policy_claims() {
+        let mut appraisal = Appraisal::new();
+        appraisal.set_policy_claim("debug-enabled", false);
+        appraisal.set_policy_claim("min-svn", 3);
+        appraisal.set_policy_claim(-1, "acme");
+
+        assert_eq!(appraisal.policy_claim_bool("debug-enabled"), Some(false));
+        assert_eq!(appraisal.policy_claim_i64("min-svn"), Some(3));
+        assert_eq!(appraisal.policy_claim_str(-1), Some("acme"));
+        assert_eq!(appraisal.policy_claim_i64("debug-enabled"), None);
+        assert_eq!(appraisal.policy_claim("missing"), None);
+
+        appraisal.set_policy_claim("min-svn", 4);
+        assert_eq!(
+            appraisal.policy_claim("min-svn"),
+            Some(&RawValue::Integer(4))
+        );
+
+        let val = serde_json::to_string(&appraisal).unwrap();
+        assert_eq!(
+            val,
+            r#"{"ear.status":"none","ear.veraison.policy-claims":{"-1":"acme","debug-enabled":false,"min-svn":4}}"#
+        );
+    }
+
+    #[test]
+    fn integer_evidence_keys() {
+        let buf: Vec<u8> = vec![
+            0xa2, // map (2)
+            0x19, 0x03, 0xe8, // 1000
+            0x02, // 2
+            0x3a, 0x00, 0x01, 0x11, 0x6f, // -70000
+            0xa3, // map (3)
+            0x01, // 1
+            0x63, 0x66, 0x6f, 0x6f, // "foo"
+            0x20, // -1
+            0x18, 0x2a, // 42
+            0x63, 0x62, 0x61, 0x72, // "bar"
+            0xf5, // true
+        ];
+
+        let appraisal: Appraisal = from_reader(buf.as_slice()).unwrap();
+        assert_eq!(appraisal.status, TrustTier::Affirming);
+        assert_eq!(appraisal.evidence_str(1), Some("foo"));
+        assert_eq!(appraisal.evidence_i64(-1), Some(42));
+        assert_eq!(
+            appraisal.annotated_evidence.get(&MapKey::from("bar")),
+            Some(&RawValue::Bool(true))
+        );
+
+        let keys: Vec<&MapKey> = appraisal.evidence_iter().map(|(k, _)| k).collect();
+        assert_eq!(
+            keys,
+            vec![
+                &MapKey::Integer(-1),
+                &MapKey::Integer(1),
+                &MapKey::Text("bar".to_string())
+            ]
+        );
+
+        let mut buf2: Vec<u8> = Vec::new();
+        into_writer(&appraisal, &mut buf2).unwrap();
+        let appraisal2: Appraisal = from_reader(buf2.as_slice()).unwrap();
+        assert_eq!(appraisal, appraisal2);
+
+        // JSON object keys must be strings, so integer keys are stringified
+        let val = serde_json::to_string(&appraisal).unwrap();
+        assert_eq!(
+            val,
+            r#"{"ear.status":"affirming","ear.veraison.annotated-evidence":{"-1":42,"1":"foo","bar":true}}"#
+        );
+
+        let appraisal2: Appraisal = serde_json::from_str(val.as_str()).unwrap();
+        assert_eq!(appraisal2.evidence_str("1"), Some("foo"));
+        assert_eq!(appraisal2.evidence_str(1), None);
+    }
+
+    #[test]
+    fn status_consistency() {
+        let mut appraisal = Appraisal::new();
+        appraisal.check_status_consistency().unwrap();
+
+        appraisal
+            .trust_vector
+            .instance_identity
+            .set(claim::TRUSTWORTHY_INSTANCE);
+        appraisal
+            .trust_vector
+            .hardware
+            .set(claim::CONTRAINDICATED_HARDWARE);
+        appraisal.status = TrustTier::Affirming;
+
+        let res = appraisal.check_status_consistency();
+        assert!(matches!(res, Err(Error::ValidationError(t))
+                if t == "status Affirming is lower than Contraindicated tier of hardware claim \
+                         (contraindicated_hw)"));
+
+        appraisal.update_status_from_trust_vector();
+        assert_eq!(appraisal.status, TrustTier::Contraindicated);
+        appraisal.check_status_consistency().unwrap();
+    }
+
+    #[test]
+    fn set_claims() {
+        use crate::trust::claim::{APPROVED_CONFIG, UNSAFE_RUNTIME};
+
+        let mut appraisal = Appraisal::new();
+        assert_eq!(appraisal.set_claims().count(), 0);
+
+        appraisal.trust_vector.executables.set(UNSAFE_RUNTIME);
+        appraisal.trust_vector.configuration.set(APPROVED_CONFIG);
+
+        assert_eq!(
+            appraisal.set_claims().collect::<Vec<_>>(),
+            vec![
+                ("configuration", APPROVED_CONFIG, TrustTier::Affirming),
+                ("executables", UNSAFE_RUNTIME, TrustTier::Warning),
+            ]
+        );
+    }
+
+    #[test]
+    fn status_driver() {
+        use crate::trust::claim::{
+            APPROVED_CONFIG, CONTRAINDICATED_HARDWARE, CONTRAINDICATED_SOURCES, UNSAFE_RUNTIME,
+        };
+
+        let mut appraisal = Appraisal::new();
+        assert_eq!(appraisal.status_driver(), None);
+
+        appraisal.trust_vector.configuration.set(APPROVED_CONFIG);
+        appraisal.trust_vector.executables.set(UNSAFE_RUNTIME);
+        appraisal
+            .trust_vector
+            .hardware
+            .set(CONTRAINDICATED_HARDWARE);
+        appraisal
+            .trust_vector
+            .sourced_data
+            .set(CONTRAINDICATED_SOURCES);
+        appraisal.update_status_from_trust_vector();
+
+        assert_eq!(appraisal.status, TrustTier::Contraindicated);
+        // ties resolve to the claim with the lowest key
+        assert_eq!(
+            appraisal.status_driver(),
+            Some(("hardware", CONTRAINDICATED_HARDWARE))
+        );
+
+        appraisal.trust_vector.hardware.unset();
+        appraisal.trust_vector.sourced_data.unset();
+        assert_eq!(
+            appraisal.status_driver(),
+            Some(("executables", UNSAFE_RUNTIME))
+        );
+    }
+
+    #[test]
+    fn debug_status() {
+        use super::AppraisalVisitor;
+        use serde::Deserializer;
+
+        for status in [
+            DebugStatus::Enabled,
+            DebugStatus::Disabled,
+            DebugStatus::DisabledSinceBoot,
+            DebugStatus::DisabledPermanently,
+            DebugStatus::DisabledFullyAndPermanently,
+        ] {
+            let mut appraisal = Appraisal::new();
+            appraisal.debug_status = Some(status);
+
+            let val = serde_json::to_string(&appraisal).unwrap();
+            assert_eq!(
+                val,
+                format!(r#"{{"ear.status":"none","dbgstat":{}}}"#, status.value())
+            );
+            let appraisal2: Appraisal = serde_json::from_str(val.as_str()).unwrap();
+            assert_eq!(appraisal2, appraisal);
+
+            // names are only accepted by lenient decoding
+            let named = format!(r#"{{"ear.status":"none","dbgstat":"{}"}}"#, status.name());
+            let res: Result<Appraisal, _> = serde_json::from_str(named.as_str());
+            assert!(res.is_err());
+
+            let appraisal2 = serde_json::Deserializer::from_str(named.as_str())
+                .deserialize_map(AppraisalVisitor {
+                    is_human_readable: true,
+                    lenient: true,
+                })
+                .unwrap();
+            assert_eq!(appraisal2, appraisal);
+
+            let mut buf: Vec<u8> = Vec::new();
+            into_writer(&appraisal, &mut buf).unwrap();
+            assert_eq!(
+                buf,
+                vec![
+                    0xbf, // map (indefinite length)
+                    0x19,
+                    0x03,
+                    0xe8, // 1000
+                    0x00, // 0
+                    0x19,
+                    0x01,
+                    0x07, // 263
+                    status.value(),
+                    0xff, // break
+                ]
+            );
+            let appraisal2: Appraisal = from_reader(buf.as_slice()).unwrap();
+            assert_eq!(appraisal2, appraisal);
+        }
+    }
+
+    #[test]
+    fn appraisal_time() {
+        let mut appraisal = Appraisal::new();
+        assert_eq!(appraisal.appraisal_system_time(), None);
+
+        appraisal.set_appraisal_system_time(UNIX_EPOCH + Duration::from_millis(1_666_091_373_500));
+        assert_eq!(appraisal.appraisal_time, Some(1_666_091_373));
+        assert_eq!(
+            appraisal.appraisal_system_time(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_666_091_373))
+        );
+
+        let val = serde_json::to_string(&appraisal).unwrap();
+        assert_eq!(
+            val,
+            r#"{"ear.status":"none","ear.veraison.appraisal-time":1666091373}"#
+        );
+        let appraisal2: Appraisal = serde_json::from_str(val.as_str()).unwrap();
+        assert_eq!(appraisal2, appraisal);
+
+        let mut buf: Vec<u8> = Vec::new();
+        into_writer(&appraisal, &mut buf).unwrap();
+        assert_eq!(
+            buf,
+            vec![
+                0xbf, // map (indefinite length)
+                0x19, 0x03, 0xe8, // 1000
+                0x00, // 0
+                0x3a, 0x00, 0x01, 0x11, 0x73, // -70004
+                0x1a, 0x63, 0x4e, 0x89, 0x6d, // 1666091373
+                0xff, // break
+            ]
+        );
+        let appraisal2: Appraisal = from_reader(buf.as_slice()).unwrap();
+        assert_eq!(appraisal2, appraisal);
+
+        appraisal.set_appraisal_system_time(UNIX_EPOCH - Duration::from_secs(60));
+        assert_eq!(appraisal.appraisal_time, Some(-60));
+        assert_eq!(
+            appraisal.appraisal_system_time(),
+            Some(UNIX_EPOCH - Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn decode_warnings() {
+        let appraisal: Appraisal = serde_json::from_str(
+            r#"{"ear.status":"affirming","ear.trustworthiness-vector":{"hardware":96}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            appraisal.warnings(),
+            [
+                "status Affirming is lower than Contraindicated tier of hardware claim \
+              (contraindicated_hw)"
+            ]
+        );
+        assert!(matches!(
+            appraisal.check_status_consistency(),
+            Err(Error::ValidationError(_))
+        ));
+
+        let appraisal: Appraisal = serde_json::from_str(
+            r#"{"ear.status":"contraindicated","ear.trustworthiness-vector":{"hardware":96}}"#,
+        )
+        .unwrap();
+        assert!(appraisal.warnings().is_empty());
+    }
+
+    #[test]
+    fn verify_evidence_digests() {
+        let blob = b"evidence blob".to_vec();
+
+        let mut appraisal = Appraisal::new();
+        appraisal.annotated_evidence.insert(
+            MapKey::from("blob"),
+            RawValue::Bytes(Bytes::from(blob.clone())),
+        );
+        appraisal
+            .annotated_evidence
+            .insert(MapKey::from("label"), RawValue::String("foo".to_string()));
+        appraisal.set_policy_claim(
+            "blob.sha-256",
+            RawValue::Bytes(Bytes::from(DigestAlg::Sha256.digest(&blob))),
+        );
+        appraisal.set_policy_claim(
+            "blob.sha-512",
+            RawValue::Bytes(Bytes::from(DigestAlg::Sha512.digest(&blob))),
+        );
+        // not a digest declaration
+        appraisal.set_policy_claim("blob.md5", RawValue::Integer(7));
+
+        appraisal.verify_evidence_digests().unwrap();
+
+        // digests are base64-encoded strings in JSON
+        let val = serde_json::to_string(&appraisal).unwrap();
+        let decoded: Appraisal = serde_json::from_str(&val).unwrap();
+        decoded.verify_evidence_digests().unwrap();
+
+        appraisal.set_policy_claim(
+            "blob.sha-384",
+            RawValue::Bytes(Bytes::from(DigestAlg::Sha384.digest(b"something else"))),
+        );
+        let res = appraisal.verify_evidence_digests();
+        assert!(matches!(res, Err(Error::ValidationError(t))
+                if t == "policy claim blob.sha-384: digest does not match annotated evidence claim blob"));
+
+        let mut appraisal = Appraisal::new();
+        appraisal
+            .annotated_evidence
+            .insert(MapKey::from("label"), RawValue::Integer(1));
+
+        appraisal.set_policy_claim("label.sha-256", RawValue::Integer(1));
+        let res = appraisal.verify_evidence_digests();
+        assert!(matches!(res, Err(Error::ValidationError(t))
+                if t == "policy claim label.sha-256: digest is not a byte string"));
+
+        appraisal.set_policy_claim("label.sha-256", RawValue::Bytes(Bytes::from(vec![0; 32])));
+        let res = appraisal.verify_evidence_digests();
+        assert!(matches!(res, Err(Error::ValidationError(t))
+                if t == "policy claim label.sha-256: annotated evidence claim label is not a byte string"));
+
+        appraisal.policy_claims.clear();
+        appraisal.set_policy_claim("other.sha-256", RawValue::Bytes(Bytes::from(vec![0; 32])));
+        let res = appraisal.verify_evidence_digests();
+        assert!(matches!(res, Err(Error::ValidationError(t))
+                if t == "policy claim other.sha-256: no annotated evidence claim other"));
+    }
 }
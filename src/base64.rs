@@ -1,6 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use base64::{self, engine::general_purpose, Engine as _};
+use base64::{
+    self, alphabet,
+    engine::{general_purpose, DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine as _,
+};
 use serde::{
     de::{self, Deserialize, Visitor},
     ser::{Serialize, Serializer},
@@ -8,15 +12,38 @@ use serde::{
 
 use crate::error::Error;
 
+// url-safe alphabet, accepting input with or without padding
+const URL_SAFE_INDIFFERENT: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
 /// decodes bytes from a base64-encoded string
+///
+/// Both the url-safe and the standard alphabets are accepted, with or without padding. (Output is
+/// always url-safe without padding.)
 pub fn decode_str(v: &str) -> Result<Vec<u8>, Error> {
-    general_purpose::URL_SAFE_NO_PAD
-        .decode(v)
+    let normalized: String = v
+        .chars()
+        .map(|c| match c {
+            '+' => '-',
+            '/' => '_',
+            c => c,
+        })
+        .collect();
+
+    URL_SAFE_INDIFFERENT
+        .decode(normalized)
         .map_err(|e| Error::ParseError(e.to_string()))
 }
 
+/// encodes bytes into a url-safe base64 string without padding
+pub fn encode_str(v: &[u8]) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(v)
+}
+
 /// a `Vec<u8>` encoded as base64 in human readable serialization
-#[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
 pub struct Bytes(Vec<u8>);
 
 impl Bytes {
@@ -41,14 +68,17 @@ impl From<&[u8]> for Bytes {
     }
 }
 
+impl From<Vec<u8>> for Bytes {
+    fn from(v: Vec<u8>) -> Self {
+        Self(v)
+    }
+}
+
 impl TryFrom<&str> for Bytes {
     type Error = Error;
 
     fn try_from(v: &str) -> Result<Self, Error> {
-        general_purpose::URL_SAFE_NO_PAD
-            .decode(v)
-            .map(Bytes)
-            .map_err(|e| Error::ParseError(e.to_string()))
+        decode_str(v).map(Bytes)
     }
 }
 
@@ -100,3 +130,46 @@ impl Visitor<'_> for BytesVisitor {
         Ok(Bytes::from(v))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let expected = decode_str("G8fAud93NgCg8C_0bY1YqVZ5zNlkb-cNsGTQia7m0is").unwrap();
+        assert_eq!(expected.len(), 32);
+
+        let res = decode_str("G8fAud93NgCg8C/0bY1YqVZ5zNlkb+cNsGTQia7m0is").unwrap();
+        assert_eq!(res, expected);
+
+        let res = decode_str("G8fAud93NgCg8C/0bY1YqVZ5zNlkb+cNsGTQia7m0is=").unwrap();
+        assert_eq!(res, expected);
+
+        let res = decode_str("G8fAud93NgCg8C_0bY1YqVZ5zNlkb-cNsGTQia7m0is=").unwrap();
+        assert_eq!(res, expected);
+
+        let bytes = Bytes::try_from("G8fAud93NgCg8C/0bY1YqVZ5zNlkb+cNsGTQia7m0is=").unwrap();
+        assert_eq!(bytes.as_slice(), expected.as_slice());
+
+        assert!(decode_str("not base64!").is_err());
+    }
+
+    #[cfg(feature = "serde_cbor")]
+    #[test]
+    fn serde_cbor() {
+        let bytes = Bytes::from([0xde, 0xad, 0xbe, 0xef].as_slice());
+
+        let buf = serde_cbor::to_vec(&bytes).unwrap();
+        assert_eq!(buf, vec![0x44, 0xde, 0xad, 0xbe, 0xef]);
+
+        let bytes2: Bytes = serde_cbor::from_slice(&buf).unwrap();
+        assert_eq!(bytes2, bytes);
+
+        let val: serde_cbor::Value = serde_cbor::from_slice(&buf).unwrap();
+        assert_eq!(val, serde_cbor::Value::Bytes(vec![0xde, 0xad, 0xbe, 0xef]));
+
+        let bytes2: Bytes = serde_cbor::value::from_value(val).unwrap();
+        assert_eq!(bytes2, bytes);
+    }
+}
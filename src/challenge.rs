@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/// A store of the challenges issued by a relying party that have not yet been answered
+///
+/// This allows replay protection to be enforced when verifying EARs (see
+/// [`Ear::verify_nonce_against_store`](crate::Ear::verify_nonce_against_store)). Implementations
+/// must check and consume a challenge atomically, so that it cannot be consumed twice by
+/// concurrent verifications.
+///
+/// Challenges that are text strings (as used in EARs signed as JWT) are passed as their UTF-8
+/// encoding.
+pub trait ChallengeStore {
+    /// Consume the specified challenge, returning `true` if it had been issued and had not yet
+    /// been consumed, and `false` otherwise
+    fn take(&self, nonce: &[u8]) -> bool;
+
+    /// Consume all of the specified (distinct) challenges, returning `true` if every one of them
+    /// had been issued and had not yet been consumed, and `false` otherwise
+    ///
+    /// This must be all or nothing: when `false` is returned, none of the challenges may have been
+    /// consumed. Checking and consuming them must therefore happen atomically (e.g. under a single
+    /// lock), so that a concurrent verification cannot consume one of them in between.
+    fn take_all(&self, nonces: &[&[u8]]) -> bool;
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::de::{self, Deserialize, Visitor};
+use serde::ser::{Serialize, SerializeMap};
+
+use crate::ear::Ear;
+use crate::error::Error;
+use crate::trust::tier::TrustTier;
+
+/// The condensed form of an attestation result
+///
+/// This is the minimal, machine-consumable verdict defined by AR4SI: the overall trustworthiness
+/// tier of the attester (see [`Ear::overall_status`]), along with the tier of each of its submods.
+/// It is intended for relying parties that do not need the full EAR.
+///
+/// It is serialized using the same names (in JSON) and keys (in CBOR) as the corresponding EAR
+/// claims: `"ear.status"` (1000) for the overall tier, and `"submods"` (266) for the per-submod
+/// tiers.
+///
+/// Unlike [`EarSummary`](crate::EarSummary), which is a report about an EAR (for dashboards and
+/// monitoring systems), this is an attestation result in its own right, and so may be deserialized
+/// by the relying party that consumes it.
+#[derive(Debug, PartialEq)]
+pub struct CondensedResult {
+    /// The overall trustworthiness tier
+    pub status: TrustTier,
+    /// The trustworthiness tier of each submod
+    pub submods: BTreeMap<String, TrustTier>,
+}
+
+impl From<&Ear> for CondensedResult {
+    fn from(ear: &Ear) -> Self {
+        CondensedResult {
+            status: ear.overall_status(),
+            submods: ear.submod_statuses(),
+        }
+    }
+}
+
+impl Serialize for CondensedResult {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let is_human_readable = serializer.is_human_readable();
+        let mut map = serializer.serialize_map(Some(2))?;
+
+        if is_human_readable {
+            map.serialize_entry("ear.status", &self.status)?;
+            map.serialize_entry("submods", &self.submods)?;
+        } else {
+            map.serialize_entry(&1000, &self.status)?;
+            map.serialize_entry(&266, &self.submods)?;
+        }
+
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for CondensedResult {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let is_hr = deserializer.is_human_readable();
+
+        deserializer.deserialize_map(CondensedResultVisitor {
+            is_human_readable: is_hr,
+        })
+    }
+}
+
+struct CondensedResultVisitor {
+    pub is_human_readable: bool,
+}
+
+impl<'de> Visitor<'de> for CondensedResultVisitor {
+    type Value = CondensedResult;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a CBOR map or JSON object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut condensed = CondensedResult {
+            status: TrustTier::None,
+            submods: BTreeMap::new(),
+        };
+
+        loop {
+            if self.is_human_readable {
+                match map.next_key::<&str>()? {
+                    Some("ear.status") => condensed.status = map.next_value::<TrustTier>()?,
+                    Some("submods") => condensed.submods = map.next_value()?,
+                    Some(s) => return Err(de::Error::custom(Error::InvalidName(s.to_string()))),
+                    None => break,
+                }
+            } else {
+                // !is_human_readable
+                match map.next_key::<i32>()? {
+                    Some(1000) => condensed.status = map.next_value::<TrustTier>()?,
+                    Some(266) => condensed.submods = map.next_value()?,
+                    Some(x) => return Err(de::Error::custom(Error::InvalidKey(x))),
+                    None => break,
+                }
+            }
+        }
+
+        Ok(condensed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{claim, Appraisal};
+
+    #[test]
+    fn serialize() {
+        let mut ear = Ear::new();
+
+        let mut appraisal = Appraisal::new();
+        appraisal.trust_vector.hardware.set(claim::GENUINE_HARDWARE);
+        appraisal.update_status_from_trust_vector();
+        ear.submods.insert("cpu".to_string(), appraisal);
+
+        let mut appraisal = Appraisal::new();
+        appraisal
+            .trust_vector
+            .executables
+            .set(claim::UNRECOGNIZED_RUNTIME);
+        appraisal.update_status_from_trust_vector();
+        ear.submods.insert("gpu".to_string(), appraisal);
+
+        ear.submods.insert("nic".to_string(), Appraisal::new());
+
+        let condensed = ear.to_condensed();
+        assert_eq!(condensed.status, TrustTier::Warning);
+        assert_eq!(condensed.submods["cpu"], TrustTier::Affirming);
+
+        let val = serde_json::to_string(&condensed).unwrap();
+        assert_eq!(
+            val,
+            r#"{"ear.status":"warning","submods":{"cpu":"affirming","gpu":"warning","nic":"none"}}"#
+        );
+
+        let mut buf: Vec<u8> = Vec::new();
+        ciborium::ser::into_writer(&condensed, &mut buf).unwrap();
+        assert_eq!(
+            buf,
+            vec![
+                0xa2, // map(2)
+                0x19, 0x03, 0xe8, // 1000
+                0x18, 0x20, // 32
+                0x19, 0x01, 0x0a, // 266
+                0xa3, // map(3)
+                0x63, 0x63, 0x70, 0x75, // "cpu"
+                0x02, // 2
+                0x63, 0x67, 0x70, 0x75, // "gpu"
+                0x18, 0x20, // 32
+                0x63, 0x6e, 0x69, 0x63, // "nic"
+                0x00, // 0
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        let mut ear = Ear::new();
+
+        let mut appraisal = Appraisal::new();
+        appraisal.trust_vector.hardware.set(claim::UNSAFE_HARDWARE);
+        appraisal.update_status_from_trust_vector();
+        ear.submods.insert("cpu".to_string(), appraisal);
+        ear.submods.insert("gpu".to_string(), Appraisal::new());
+
+        let condensed = ear.to_condensed();
+
+        let val = serde_json::to_string(&condensed).unwrap();
+        let condensed2: CondensedResult = serde_json::from_str(&val).unwrap();
+        assert_eq!(condensed2, condensed);
+
+        let mut buf: Vec<u8> = Vec::new();
+        ciborium::ser::into_writer(&condensed, &mut buf).unwrap();
+        let condensed2: CondensedResult = ciborium::de::from_reader(buf.as_slice()).unwrap();
+        assert_eq!(condensed2, condensed);
+
+        let res = serde_json::from_str::<CondensedResult>(r#"{"ear.status":"warning","x":1}"#);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "invalid name: x at line 1 column 27"
+        );
+    }
+}
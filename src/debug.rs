@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{
+    de::{self, Visitor},
+    ser::{Serialize, Serializer},
+    Deserialize,
+};
+use std::fmt;
+
+use crate::TrustTier;
+
+/// The debug status of an attested environment, as conveyed by the EAT `dbgstat` claim
+///
+/// See <https://www.rfc-editor.org/rfc/rfc9711#section-4.2.9>. As `dbgstat-type` is an integer
+/// enumeration, this is serialized as its value (e.g. `2`) in both JSON and CBOR. Its name (e.g.
+/// `"disabled-since-boot"`) is only accepted by lenient decoding (see
+/// [`Ear::from_json_lenient`](crate::Ear::from_json_lenient)).
+///
+/// Debug facilities that are (or were, during the current boot cycle) enabled may be used to
+/// inspect or modify the state of the environment, undermining other claims made about it. See
+/// [`DebugStatus::suggested_tier`] for a suggested mapping onto trustworthiness tiers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DebugStatus {
+    /// Debug is currently enabled
+    Enabled,
+    /// Debug is currently disabled, though it may have been enabled earlier (e.g. during boot)
+    Disabled,
+    /// Debug has been disabled since boot
+    DisabledSinceBoot,
+    /// Debug is permanently disabled (though the manufacturer may be able to re-enable it)
+    DisabledPermanently,
+    /// Debug is permanently disabled, including for the manufacturer
+    DisabledFullyAndPermanently,
+}
+
+impl DebugStatus {
+    /// Return the value of the debug status (as used in serialization)
+    pub fn value(&self) -> u8 {
+        match self {
+            DebugStatus::Enabled => 0,
+            DebugStatus::Disabled => 1,
+            DebugStatus::DisabledSinceBoot => 2,
+            DebugStatus::DisabledPermanently => 3,
+            DebugStatus::DisabledFullyAndPermanently => 4,
+        }
+    }
+
+    /// Return the name of the debug status
+    pub fn name(&self) -> &'static str {
+        match self {
+            DebugStatus::Enabled => "enabled",
+            DebugStatus::Disabled => "disabled",
+            DebugStatus::DisabledSinceBoot => "disabled-since-boot",
+            DebugStatus::DisabledPermanently => "disabled-permanently",
+            DebugStatus::DisabledFullyAndPermanently => "disabled-fully-and-permanently",
+        }
+    }
+
+    /// Return the trustworthiness tier suggested for an environment with this debug status
+    ///
+    /// An environment that has, or may have had, debug enabled since it was booted is a warning;
+    /// otherwise, debug does not affect the trustworthiness of the environment. This is only
+    /// guidance: a verifier's policy may well be stricter (e.g. treating enabled debug in a
+    /// production device as contraindicated).
+    pub fn suggested_tier(&self) -> TrustTier {
+        match self {
+            DebugStatus::Enabled | DebugStatus::Disabled => TrustTier::Warning,
+            DebugStatus::DisabledSinceBoot
+            | DebugStatus::DisabledPermanently
+            | DebugStatus::DisabledFullyAndPermanently => TrustTier::Affirming,
+        }
+    }
+}
+
+impl fmt::Display for DebugStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Serialize for DebugStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u8(self.value())
+    }
+}
+
+impl<'de> Deserialize<'de> for DebugStatus {
+    fn deserialize<D>(deserializer: D) -> Result<DebugStatus, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DebugStatusVisitor { lenient: false })
+    }
+}
+
+// A DebugStatus that may also be decoded from its name, as some producers encode it. This is only
+// used for lenient decoding (see Ear::from_json_lenient).
+pub(crate) struct LenientDebugStatus(pub DebugStatus);
+
+impl<'de> Deserialize<'de> for LenientDebugStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(DebugStatusVisitor { lenient: true })
+            .map(LenientDebugStatus)
+    }
+}
+
+fn unexpected_debug_status<E: de::Error>(value: impl fmt::Display) -> E {
+    E::custom(format!("Unexpected DebugStatus value: {value}"))
+}
+
+struct DebugStatusVisitor {
+    // accept names (see LenientDebugStatus)
+    lenient: bool,
+}
+
+impl Visitor<'_> for DebugStatusVisitor {
+    type Value = DebugStatus;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an integer between 0 and 4")
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match u64::try_from(value) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => Err(unexpected_debug_status(value)),
+        }
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match value {
+            0 => Ok(DebugStatus::Enabled),
+            1 => Ok(DebugStatus::Disabled),
+            2 => Ok(DebugStatus::DisabledSinceBoot),
+            3 => Ok(DebugStatus::DisabledPermanently),
+            4 => Ok(DebugStatus::DisabledFullyAndPermanently),
+            _ => Err(unexpected_debug_status(value)),
+        }
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        if !self.lenient {
+            return Err(E::invalid_type(de::Unexpected::Str(value), &self));
+        }
+
+        match value {
+            "enabled" => Ok(DebugStatus::Enabled),
+            "disabled" => Ok(DebugStatus::Disabled),
+            "disabled-since-boot" => Ok(DebugStatus::DisabledSinceBoot),
+            "disabled-permanently" => Ok(DebugStatus::DisabledPermanently),
+            "disabled-fully-and-permanently" => Ok(DebugStatus::DisabledFullyAndPermanently),
+            _ => Err(unexpected_debug_status(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use ciborium::{de::from_reader, ser::into_writer};
+
+    const ALL: [DebugStatus; 5] = [
+        DebugStatus::Enabled,
+        DebugStatus::Disabled,
+        DebugStatus::DisabledSinceBoot,
+        DebugStatus::DisabledPermanently,
+        DebugStatus::DisabledFullyAndPermanently,
+    ];
+
+    #[test]
+    fn serde() {
+        for status in ALL {
+            let val = serde_json::to_string(&status).unwrap();
+            assert_eq!(val, status.value().to_string());
+
+            let status2: DebugStatus = serde_json::from_str(&val).unwrap();
+            assert_eq!(status, status2);
+
+            let name = format!("\"{}\"", status.name());
+            let res: Result<DebugStatus, _> = serde_json::from_str(&name);
+            assert!(res.is_err());
+
+            let status2: LenientDebugStatus = serde_json::from_str(&name).unwrap();
+            assert_eq!(status, status2.0);
+
+            let mut buf: Vec<u8> = Vec::new();
+            into_writer(&status, &mut buf).unwrap();
+            assert_eq!(buf, vec![status.value()]);
+
+            let status2: DebugStatus = from_reader(buf.as_slice()).unwrap();
+            assert_eq!(status, status2);
+        }
+
+        let res: Result<LenientDebugStatus, _> = serde_json::from_str("\"off\"");
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "Unexpected DebugStatus value: off at line 1 column 5"
+        );
+
+        let res: Result<DebugStatus, _> = from_reader([0x05].as_slice());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Unexpected DebugStatus value: 5"));
+    }
+
+    #[test]
+    fn suggested_tier() {
+        assert_eq!(DebugStatus::Enabled.suggested_tier(), TrustTier::Warning);
+        assert_eq!(DebugStatus::Disabled.suggested_tier(), TrustTier::Warning);
+        assert_eq!(
+            DebugStatus::DisabledSinceBoot.suggested_tier(),
+            TrustTier::Affirming
+        );
+    }
+}
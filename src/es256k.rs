@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// ECDSA using secp256k1 and SHA-256 (ES256K), as defined by RFC 8812.
+//
+// jsonwebtoken (as of version 9) does not support this algorithm, so JWTs are signed and
+// verified here directly, applying the same checks as the jsonwebtoken Validation used for the
+// other algorithms (see Ear::from_jwt). COSE signing and verification go through the cose lib,
+// which only needs the curve mapping.
+
+use openssl::{bn, ec, ecdsa, nid::Nid, pkey};
+
+use crate::base64;
+use crate::ear::checked_jwk_value;
+use crate::error::Error;
+
+// JWS "alg" header value
+const JWS_ALG: &str = "ES256K";
+
+// Leeway (in seconds) allowed when checking "exp", as for jsonwebtoken's default Validation
+const EXP_LEEWAY: u64 = 60;
+
+// Size (in bytes) of a secp256k1 coordinate or scalar
+const FIELD_SIZE: i32 = 32;
+
+// Load a PEM- or DER-encoded secp256k1 private key.
+pub(crate) fn private_key(key: &[u8], der: bool) -> Result<ec::EcKey<pkey::Private>, Error> {
+    let ec_key = if der {
+        ec::EcKey::private_key_from_der(key)
+    } else {
+        ec::EcKey::private_key_from_pem(key)
+    }
+    .map_err(|e| Error::KeyError(e.to_string()))?;
+
+    if ec_key.group().curve_name() != Some(Nid::SECP256K1) {
+        return Err(Error::KeyError("key is not a secp256k1 key".to_string()));
+    }
+
+    Ok(ec_key)
+}
+
+// Load a PEM- or DER-encoded (SubjectPublicKeyInfo) secp256k1 public key.
+pub(crate) fn public_key(key: &[u8], der: bool) -> Result<ec::EcKey<pkey::Public>, Error> {
+    let ec_key = if der {
+        ec::EcKey::public_key_from_der(key)
+    } else {
+        ec::EcKey::public_key_from_pem(key)
+    }
+    .map_err(|e| Error::KeyError(e.to_string()))?;
+
+    if ec_key.group().curve_name() != Some(Nid::SECP256K1) {
+        return Err(Error::KeyError("key is not a secp256k1 key".to_string()));
+    }
+
+    Ok(ec_key)
+}
+
+// Return the x and y coordinates of a JWK-encoded secp256k1 public key.
+pub(crate) fn jwk_coordinates(key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let value = checked_jwk_value(key)?;
+
+    if value["kty"] != "EC" || value["crv"] != "secp256k1" {
+        return Err(Error::KeyError("JWK is not a secp256k1 EC key".to_string()));
+    }
+
+    // checked_jwk_value() has ensured that x and y are present, and are strings
+    let x = base64::decode_str(value["x"].as_str().unwrap())?;
+    let y = base64::decode_str(value["y"].as_str().unwrap())?;
+
+    Ok((x, y))
+}
+
+// Load a JWK-encoded secp256k1 public key.
+pub(crate) fn public_key_from_jwk(key: &[u8]) -> Result<ec::EcKey<pkey::Public>, Error> {
+    let (x, y) = jwk_coordinates(key)?;
+
+    let x = bn::BigNum::from_slice(&x).map_err(|e| Error::KeyError(e.to_string()))?;
+    let y = bn::BigNum::from_slice(&y).map_err(|e| Error::KeyError(e.to_string()))?;
+
+    let group =
+        ec::EcGroup::from_curve_name(Nid::SECP256K1).map_err(|e| Error::KeyError(e.to_string()))?;
+
+    ec::EcKey::from_public_key_affine_coordinates(&group, &x, &y)
+        .map_err(|e| Error::KeyError(e.to_string()))
+}
+
+// Sign the data, returning the signature as the concatenation of r and s.
+pub(crate) fn sign(data: &[u8], key: &ec::EcKey<pkey::Private>) -> Result<Vec<u8>, Error> {
+    let digest = openssl::sha::sha256(data);
+    let sig = ecdsa::EcdsaSig::sign(&digest, key).map_err(|e| Error::SignError(e.to_string()))?;
+
+    let mut out = sig
+        .r()
+        .to_vec_padded(FIELD_SIZE)
+        .map_err(|e| Error::SignError(e.to_string()))?;
+    out.extend(
+        sig.s()
+            .to_vec_padded(FIELD_SIZE)
+            .map_err(|e| Error::SignError(e.to_string()))?,
+    );
+
+    Ok(out)
+}
+
+// Verify a signature (the concatenation of r and s) over the data.
+pub(crate) fn verify(
+    data: &[u8],
+    sig: &[u8],
+    key: &ec::EcKey<pkey::Public>,
+) -> Result<bool, Error> {
+    if sig.len() != 2 * FIELD_SIZE as usize {
+        return Ok(false);
+    }
+
+    let (r, s) = sig.split_at(FIELD_SIZE as usize);
+    let r = bn::BigNum::from_slice(r).map_err(|e| Error::VerifyError(e.to_string()))?;
+    let s = bn::BigNum::from_slice(s).map_err(|e| Error::VerifyError(e.to_string()))?;
+    let sig = ecdsa::EcdsaSig::from_private_components(r, s)
+        .map_err(|e| Error::VerifyError(e.to_string()))?;
+
+    let digest = openssl::sha::sha256(data);
+    sig.verify(&digest, key)
+        .map_err(|e| Error::VerifyError(e.to_string()))
+}
+
+// Encode the (JSON-encoded) claims as a JWT, signing it with the key, and setting the cty header
+// if specified.
+pub(crate) fn encode_jwt(
+    claims: &[u8],
+    key: &ec::EcKey<pkey::Private>,
+    cty: Option<&str>,
+) -> Result<String, Error> {
+    let mut header = serde_json::json!({"typ": "JWT", "alg": JWS_ALG});
+    if let Some(cty) = cty {
+        header["cty"] = cty.into();
+    }
+    let header = serde_json::to_vec(&header).map_err(|e| Error::SignError(e.to_string()))?;
+
+    let signing_input = format!(
+        "{}.{}",
+        base64::encode_str(&header),
+        base64::encode_str(claims)
+    );
+    let sig = sign(signing_input.as_bytes(), key)?;
+
+    Ok(format!("{signing_input}.{}", base64::encode_str(&sig)))
+}
+
+// Verify a JWT using the key, returning its (JSON-encoded) claims.
+pub(crate) fn decode_jwt(token: &str, key: &ec::EcKey<pkey::Public>) -> Result<Vec<u8>, Error> {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(Error::VerifyError(format!(
+            "JWT must have 3 parts, but found {}",
+            parts.len()
+        )));
+    }
+
+    let header: serde_json::Value = serde_json::from_slice(&base64::decode_str(parts[0])?)
+        .map_err(|e| Error::VerifyError(e.to_string()))?;
+    if header["alg"] != JWS_ALG {
+        return Err(Error::VerifyError(format!(
+            "JWT alg header is {}, but expected {JWS_ALG}",
+            header["alg"]
+        )));
+    }
+
+    let signing_input = format!("{}.{}", parts[0], parts[1]);
+    if !verify(
+        signing_input.as_bytes(),
+        &base64::decode_str(parts[2])?,
+        key,
+    )? {
+        return Err(Error::VerifyError("invalid signature".to_string()));
+    }
+
+    let claims = base64::decode_str(parts[1])?;
+    check_exp(&claims)?;
+
+    Ok(claims)
+}
+
+// Reject the (JSON-encoded) claims if they have expired, as jsonwebtoken would (with the same
+// error), though an "exp" claim is not required.
+fn check_exp(claims: &[u8]) -> Result<(), Error> {
+    let claims: serde_json::Value =
+        serde_json::from_slice(claims).map_err(|e| Error::VerifyError(e.to_string()))?;
+
+    let exp = match claims.get("exp") {
+        None => return Ok(()),
+        Some(exp) => exp
+            .as_u64()
+            .ok_or_else(|| Error::VerifyError("InvalidClaimFormat(\"exp\")".to_string()))?,
+    };
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| Error::VerifyError(e.to_string()))?
+        .as_secs();
+
+    if exp < now.saturating_sub(EXP_LEEWAY) {
+        return Err(Error::VerifyError("ExpiredSignature".to_string()));
+    }
+
+    Ok(())
+}
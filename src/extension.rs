@@ -28,15 +28,16 @@ impl ExtensionEntry {
 
 #[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 enum CollectedKey {
-    Key(i32),
+    Key(i64),
     Name(String),
 }
 
 #[derive(Debug)]
 pub struct Extensions {
-    by_key: BTreeMap<i32, Arc<RwLock<ExtensionEntry>>>,
+    by_key: BTreeMap<i64, Arc<RwLock<ExtensionEntry>>>,
     by_name: BTreeMap<String, Arc<RwLock<ExtensionEntry>>>,
     collected: BTreeMap<CollectedKey, RawValue>,
+    preserve_unknown: bool,
 }
 
 impl Default for Extensions {
@@ -51,10 +52,18 @@ impl<'de> Extensions {
             by_key: BTreeMap::new(),
             by_name: BTreeMap::new(),
             collected: BTreeMap::new(),
+            preserve_unknown: false,
         }
     }
 
-    pub fn register(&mut self, name: &str, key: i32, kind: RawValueKind) -> Result<(), Error> {
+    pub fn register(
+        &mut self,
+        name: &str,
+        key: impl Into<i64>,
+        kind: RawValueKind,
+    ) -> Result<(), Error> {
+        let key = key.into();
+
         if self.by_name.contains_key(name) {
             return Err(Error::ExtensionError(
                 format!("name {name} already registered").to_string(),
@@ -111,7 +120,7 @@ impl<'de> Extensions {
         Ok(())
     }
 
-    pub fn have_key(&self, key: &i32) -> bool {
+    pub fn have_key(&self, key: &i64) -> bool {
         self.by_key.contains_key(key)
     }
 
@@ -119,7 +128,7 @@ impl<'de> Extensions {
         self.by_name.contains_key(name)
     }
 
-    pub fn get_by_key(&self, key: &i32) -> Option<RawValue> {
+    pub fn get_by_key(&self, key: &i64) -> Option<RawValue> {
         self.by_key
             .get(key)
             .map(|entry| entry.read().unwrap().value.clone())
@@ -131,7 +140,7 @@ impl<'de> Extensions {
             .map(|entry| entry.read().unwrap().value.clone())
     }
 
-    pub fn get_kind_by_key(&self, key: &i32) -> RawValueKind {
+    pub fn get_kind_by_key(&self, key: &i64) -> RawValueKind {
         match self.by_key.get(key) {
             Some(entry) => entry.read().unwrap().kind.clone(),
             None => RawValueKind::Null,
@@ -145,7 +154,8 @@ impl<'de> Extensions {
         }
     }
 
-    pub fn set_by_key(&mut self, key: i32, value: RawValue) -> Result<(), Error> {
+    pub fn set_by_key(&mut self, key: impl Into<i64>, value: RawValue) -> Result<(), Error> {
+        let key = key.into();
         let entry = self.by_key.get(&key).ok_or(Error::ExtensionError(
             format!("{key} not registered").to_string(),
         ))?;
@@ -181,6 +191,21 @@ impl<'de> Extensions {
         Ok(())
     }
 
+    /// Return `true` if unknown entries (i.e. those that were encountered during deserialization,
+    /// but have not been registered) will be re-emitted on serialization, and `false` otherwise
+    pub fn preserve_unknown(&self) -> bool {
+        self.preserve_unknown
+    }
+
+    /// Set whether unknown entries (i.e. those that were encountered during deserialization, but
+    /// have not been registered) will be re-emitted on serialization
+    ///
+    /// This is off by default. Unknown entries are re-emitted verbatim, but only into the format
+    /// they were deserialized from (names are used in JSON, and keys in CBOR).
+    pub fn set_preserve_unknown(&mut self, preserve: bool) {
+        self.preserve_unknown = preserve;
+    }
+
     pub(crate) fn visit_map_entry_by_name<A>(
         &mut self,
         name: &str,
@@ -204,7 +229,7 @@ impl<'de> Extensions {
         Ok(())
     }
 
-    pub(crate) fn visit_map_entry_by_key<A>(&mut self, key: i32, mut map: A) -> Result<(), A::Error>
+    pub(crate) fn visit_map_entry_by_key<A>(&mut self, key: i64, mut map: A) -> Result<(), A::Error>
     where
         A: serde::de::MapAccess<'de>,
     {
@@ -233,6 +258,17 @@ impl<'de> Extensions {
             map.serialize_entry(&name, &val.read().unwrap().value)?;
         }
 
+        if self.preserve_unknown {
+            for (ck, val) in &self.collected {
+                match ck {
+                    CollectedKey::Name(name) if !self.have_name(name) => {
+                        map.serialize_entry(name, val)?
+                    }
+                    _ => (),
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -248,6 +284,17 @@ impl<'de> Extensions {
             map.serialize_entry(&key, &val.read().unwrap().value)?;
         }
 
+        if self.preserve_unknown {
+            for (ck, val) in &self.collected {
+                match ck {
+                    CollectedKey::Key(key) if !self.have_key(key) => {
+                        map.serialize_entry(key, val)?
+                    }
+                    _ => (),
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -283,7 +330,7 @@ impl PartialEq for Extensions {
 #[derive(Debug, Clone)]
 struct RegisterEntry {
     pub name: String,
-    pub key: i32,
+    pub key: i64,
     pub kind: RawValueKind,
 }
 
@@ -291,7 +338,7 @@ struct RegisterEntry {
 struct Register {
     pub entries: Vec<RegisterEntry>,
     names: HashSet<String>,
-    keys: HashSet<i32>,
+    keys: HashSet<i64>,
 }
 
 impl Register {
@@ -303,7 +350,14 @@ impl Register {
         }
     }
 
-    pub fn register(&mut self, name: &str, key: i32, kind: RawValueKind) -> Result<(), Error> {
+    pub fn register(
+        &mut self,
+        name: &str,
+        key: impl Into<i64>,
+        kind: RawValueKind,
+    ) -> Result<(), Error> {
+        let key = key.into();
+
         match self.names.get(name) {
             Some(_) => Err(Error::ExtensionError(
                 format!("name {name} already registered").to_string(),
@@ -356,19 +410,19 @@ impl Profile {
     pub fn register_ear_extension(
         &mut self,
         name: &str,
-        key: i32,
+        key: impl Into<i64>,
         kind: RawValueKind,
     ) -> Result<(), Error> {
-        self.ear.register(name, key, kind)
+        self.ear.register(name, key.into(), kind)
     }
 
     pub fn register_appraisal_extension(
         &mut self,
         name: &str,
-        key: i32,
+        key: impl Into<i64>,
         kind: RawValueKind,
     ) -> Result<(), Error> {
-        self.appraisal.register(name, key, kind)
+        self.appraisal.register(name, key.into(), kind)
     }
 
     pub fn populate_ear_extensions(&self, ear: &mut Ear) -> Result<(), Error> {
@@ -405,6 +459,32 @@ impl Profile {
 
         Ok(())
     }
+
+    /// Ensure that the appraisal conforms to the appraisal extensions defined by this profile,
+    /// i.e. that all of them have been set, and that their values are of the registered kinds.
+    pub fn check_appraisal_conformance(&self, appraisal: &Appraisal) -> Result<(), Error> {
+        for entry in &self.appraisal.entries {
+            match appraisal.extensions.get_by_name(&entry.name) {
+                None | Some(RawValue::Null) => {
+                    return Err(Error::ProfileError(format!(
+                        "missing extension {name}",
+                        name = entry.name
+                    )))
+                }
+                Some(v) if !v.is(&entry.kind) => {
+                    return Err(Error::ProfileError(format!(
+                        "extension {name} kind mismatch: value is {vk:?}, but want {ek:?}",
+                        name = entry.name,
+                        vk = v.kind(),
+                        ek = entry.kind,
+                    )))
+                }
+                Some(_) => (),
+            }
+        }
+
+        Ok(())
+    }
 }
 
 lazy_static! {
@@ -514,6 +594,99 @@ mod test {
         assert_eq!(out, r#"{"foo":"bar"}"#);
     }
 
+    #[test]
+    fn large_integer() {
+        use ciborium::{de::from_reader, ser::into_writer};
+
+        let mut appraisal = Appraisal::new();
+        appraisal
+            .extensions
+            .register("ext.counter", -65537, RawValueKind::Integer)
+            .unwrap();
+        appraisal
+            .extensions
+            .set_by_key(-65537, RawValue::Integer(i64::MAX - 1))
+            .unwrap();
+
+        let mut buf: Vec<u8> = Vec::new();
+        into_writer(&appraisal, &mut buf).unwrap();
+
+        let mut appraisal2: Appraisal = from_reader(buf.as_slice()).unwrap();
+        appraisal2
+            .extensions
+            .register("ext.counter", -65537, RawValueKind::Integer)
+            .unwrap();
+
+        assert_eq!(
+            appraisal2.extensions.get_by_name("ext.counter").unwrap(),
+            RawValue::Integer(i64::MAX - 1)
+        );
+
+        let val = serde_json::to_string(&appraisal).unwrap();
+        let mut appraisal2: Appraisal = serde_json::from_str(&val).unwrap();
+        appraisal2
+            .extensions
+            .register("ext.counter", -65537, RawValueKind::Integer)
+            .unwrap();
+
+        assert_eq!(
+            appraisal2.extensions.get_by_key(&-65537).unwrap(),
+            RawValue::Integer(i64::MAX - 1)
+        );
+    }
+
+    #[test]
+    fn large_key() {
+        use ciborium::{de::from_reader, ser::into_writer};
+
+        let key: i64 = i64::from(i32::MAX) + 1;
+
+        let mut ear = Ear::new();
+        ear.profile = "test".to_string();
+        ear.iat = 1666529184;
+        ear.vid.build = "vsts 0.0.1".to_string();
+        ear.vid.developer = "https://veraison-project.org".to_string();
+        ear.extensions
+            .register("ext.big", key, RawValueKind::String)
+            .unwrap();
+        ear.extensions
+            .set_by_key(key, RawValue::String("big".to_string()))
+            .unwrap();
+
+        let mut appraisal = Appraisal::new();
+        appraisal
+            .extensions
+            .register("ext.bigger", i64::MIN, RawValueKind::Integer)
+            .unwrap();
+        appraisal
+            .extensions
+            .set_by_key(i64::MIN, RawValue::Integer(7))
+            .unwrap();
+        ear.submods.insert("test".to_string(), appraisal);
+
+        let mut buf: Vec<u8> = Vec::new();
+        into_writer(&ear, &mut buf).unwrap();
+
+        let mut ear2: Ear = from_reader(buf.as_slice()).unwrap();
+        ear2.extensions
+            .register("ext.big", key, RawValueKind::String)
+            .unwrap();
+        assert_eq!(
+            ear2.extensions.get_by_name("ext.big").unwrap(),
+            RawValue::String("big".to_string())
+        );
+
+        let appraisal2 = ear2.submods.get_mut("test").unwrap();
+        appraisal2
+            .extensions
+            .register("ext.bigger", i64::MIN, RawValueKind::Integer)
+            .unwrap();
+        assert_eq!(
+            appraisal2.extensions.get_by_key(&i64::MIN).unwrap(),
+            RawValue::Integer(7)
+        );
+    }
+
     #[test]
     fn value_convert() {
         let v = RawValue::String("3q2-7w".to_string());
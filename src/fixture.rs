@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs;
+use std::path::Path;
+
+use crate::base64;
+use crate::ear::{cose_sign1_items, decode_pem, Ear};
+use crate::error::Error;
+use crate::verification::VerificationKey;
+
+/// Load an EAR from a test fixture file
+///
+/// This is intended for conformance testing against test vectors produced by other
+/// implementations. The fixture may contain a JWT, a COSE_Sign1, or an unsigned JSON or CBOR EAR
+/// (optionally PEM-wrapped); the format is determined from the contents. If a key is specified,
+/// the signature of the JWT or COSE token is verified (and unsigned fixtures are rejected);
+/// otherwise, the EAR is decoded _without_ verification.
+pub fn load_fixture<P: AsRef<Path>>(path: P, key: Option<&VerificationKey>) -> Result<Ear, Error> {
+    let path = path.as_ref();
+    let mut data = fs::read(path).map_err(|e| Error::ParseError(format!("{path:?}: {e}")))?;
+
+    if let Ok(text) = std::str::from_utf8(&data) {
+        if text.trim_start().starts_with("-----BEGIN ") {
+            data = decode_pem(text)?;
+        }
+    }
+
+    let fixture = Fixture::detect(&data);
+
+    match (fixture, key) {
+        (Fixture::Json | Fixture::Cbor, Some(_)) => Err(Error::VerifyError(format!(
+            "{path:?}: fixture is not signed"
+        ))),
+        (Fixture::Jwt | Fixture::Cose, Some(k)) => Ear::from_token(&data, k),
+        (Fixture::Jwt, None) => {
+            let text = std::str::from_utf8(&data).map_err(|e| Error::ParseError(e.to_string()))?;
+            let payload = base64::decode_str(text.trim().split('.').nth(1).unwrap_or(""))?;
+            serde_json::from_slice(&payload).map_err(|e| Error::ParseError(e.to_string()))
+        }
+        (Fixture::Cose, None) => match cose_sign1_items(&data)?.swap_remove(2) {
+            ciborium::value::Value::Bytes(payload) => Ear::from_cbor(&payload),
+            _ => Err(Error::ParseError(
+                "COSE_Sign1 payload is detached".to_string(),
+            )),
+        },
+        (Fixture::Json, None) => {
+            serde_json::from_slice(&data).map_err(|e| Error::ParseError(e.to_string()))
+        }
+        (Fixture::Cbor, None) => Ear::from_cbor(&data),
+    }
+}
+
+enum Fixture {
+    Jwt,
+    Cose,
+    Json,
+    Cbor,
+}
+
+impl Fixture {
+    fn detect(data: &[u8]) -> Fixture {
+        if let Ok(text) = std::str::from_utf8(data) {
+            let text = text.trim();
+
+            if text.starts_with('{') {
+                return Fixture::Json;
+            }
+
+            if text.split('.').count() == 3 {
+                return Fixture::Jwt;
+            }
+        }
+
+        // An unsigned EAR is a CBOR map (major type 5); anything else is assumed to be a
+        // (possibly tagged) COSE_Sign1.
+        match data.first() {
+            Some(b) if b >> 5 == 5 => Fixture::Cbor,
+            _ => Fixture::Cose,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::algorithm::Algorithm;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/misc/ear.jwt");
+    const CLAIMS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/misc/claims.json");
+    const KEY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/misc/ec_key_pub.jwk");
+
+    #[test]
+    fn load() {
+        let ear = load_fixture(FIXTURE, None).unwrap();
+        assert_eq!(ear.profile, "tag:github.com,2023:veraison/ear");
+
+        let key = VerificationKey::jwk(Algorithm::ES256, &fs::read(KEY).unwrap());
+        let ear2 = load_fixture(FIXTURE, Some(&key)).unwrap();
+        assert_eq!(ear, ear2);
+
+        let ear2 = load_fixture(CLAIMS, None).unwrap();
+        assert_eq!(ear, ear2);
+
+        let res = load_fixture(CLAIMS, Some(&key));
+        assert!(matches!(res, Err(Error::VerifyError(t)) if t.ends_with("fixture is not signed")));
+    }
+}
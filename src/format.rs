@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: Apache-2.0
+
+/// Serialization formats for signed EARs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// A JSON-encoded EAR signed as a JWT
+    Jwt,
+    /// A CBOR-encoded EAR signed as a COSE_Sign1
+    Cose,
+}
+
+impl Format {
+    /// Return the media type of an EAR with the specified profile, signed in this format
+    ///
+    /// e.g. `application/eat+jwt; eat_profile="tag:github.com,2023:veraison/ear"`
+    pub fn media_type(&self, profile: &str) -> String {
+        let subtype = match self {
+            Format::Jwt => "eat+jwt",
+            Format::Cose => "eat+cwt",
+        };
+
+        format!("application/{subtype}; eat_profile=\"{profile}\"")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn media_type() {
+        assert_eq!(
+            Format::Jwt.media_type("tag:github.com,2023:veraison/ear"),
+            r#"application/eat+jwt; eat_profile="tag:github.com,2023:veraison/ear""#
+        );
+        assert_eq!(
+            Format::Cose.media_type("1.3.6.1.4.1.65535"),
+            r#"application/eat+cwt; eat_profile="1.3.6.1.4.1.65535""#
+        );
+    }
+}
@@ -32,11 +32,26 @@ impl VerifierID {
         }
 
         if self.developer.as_str() == "" {
-            return Err(Error::ValidationError("empty build".to_string()));
+            return Err(Error::ValidationError("empty developer".to_string()));
         }
 
         Ok(())
     }
+
+    /// Ensure that the developer is an absolute URI (as the EAR draft suggests it should be),
+    /// rather than e.g. a bare organization name
+    ///
+    /// This is not part of [`VerifierID::validate`], which only checks that the fields are set.
+    pub fn validate_developer_uri(&self) -> Result<(), Error> {
+        url::Url::parse(&self.developer).map_err(|e| {
+            Error::ValidationError(format!(
+                "developer {} is not an absolute URI: {e}",
+                self.developer
+            ))
+        })?;
+
+        Ok(())
+    }
 }
 
 impl Default for VerifierID {
@@ -4,6 +4,7 @@ use std::fmt;
 
 use crate::base64::Bytes;
 use crate::error::Error;
+use crate::trust::tier::TrustTier;
 use serde::{
     de::{self, Deserialize, Visitor},
     ser::{Serialize, SerializeMap},
@@ -13,12 +14,17 @@ use serde::{
 #[derive(Debug, PartialEq)]
 pub struct KeyAttestation {
     pub pub_key: Bytes,
+    /// The trustworthiness of the attested key, as assessed by the verifier
+    ///
+    /// If unset, no claim is made regarding the outcome of the key attestation.
+    pub status: Option<TrustTier>,
 }
 
 impl KeyAttestation {
     pub fn new() -> KeyAttestation {
         KeyAttestation {
             pub_key: Bytes::new(),
+            status: None,
         }
     }
 }
@@ -39,8 +45,16 @@ impl Serialize for KeyAttestation {
 
         if is_human_readable {
             map.serialize_entry("akpub", &self.pub_key)?;
+
+            if let Some(status) = &self.status {
+                map.serialize_entry("status", status)?;
+            }
         } else {
             map.serialize_entry(&0, &self.pub_key)?;
+
+            if let Some(status) = &self.status {
+                map.serialize_entry(&1, status)?;
+            }
         }
 
         map.end()
@@ -81,6 +95,7 @@ impl<'de> Visitor<'de> for KeyAttestationVisitor {
             if self.is_human_readable {
                 match map.next_key::<&str>()? {
                     Some("akpub") => key_attest.pub_key = map.next_value::<Bytes>()?,
+                    Some("status") => key_attest.status = Some(map.next_value::<TrustTier>()?),
                     Some(s) => return Err(de::Error::custom(Error::InvalidName(s.to_string()))),
                     None => break,
                 }
@@ -88,6 +103,7 @@ impl<'de> Visitor<'de> for KeyAttestationVisitor {
                 // !is_human_readable
                 match map.next_key::<i32>()? {
                     Some(0) => key_attest.pub_key = map.next_value::<Bytes>()?,
+                    Some(1) => key_attest.status = Some(map.next_value::<TrustTier>()?),
                     Some(x) => return Err(de::Error::custom(Error::InvalidKey(x))),
                     None => break,
                 }
@@ -97,3 +113,46 @@ impl<'de> Visitor<'de> for KeyAttestationVisitor {
         Ok(key_attest)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ciborium::{de::from_reader, ser::into_writer};
+
+    #[test]
+    fn serde() {
+        let mut ka = KeyAttestation::new();
+        ka.pub_key = Bytes::from([0xde, 0xad, 0xbe, 0xef].as_slice());
+
+        let val = serde_json::to_string(&ka).unwrap();
+        assert_eq!(val, r#"{"akpub":"3q2-7w"}"#);
+
+        let ka2: KeyAttestation = serde_json::from_str(&val).unwrap();
+        assert_eq!(ka2, ka);
+
+        ka.status = Some(TrustTier::Affirming);
+
+        let val = serde_json::to_string(&ka).unwrap();
+        assert_eq!(val, r#"{"akpub":"3q2-7w","status":"affirming"}"#);
+
+        let ka2: KeyAttestation = serde_json::from_str(&val).unwrap();
+        assert_eq!(ka2, ka);
+
+        let mut buf: Vec<u8> = Vec::new();
+        into_writer(&ka, &mut buf).unwrap();
+        assert_eq!(
+            buf,
+            vec![
+                0xbf, // map (indefinite length)
+                0x00, // 0
+                0x44, 0xde, 0xad, 0xbe, 0xef, // bstr (4)
+                0x01, // 1
+                0x02, // 2 (affirming)
+                0xff, // break
+            ]
+        );
+
+        let ka2: KeyAttestation = from_reader(buf.as_slice()).unwrap();
+        assert_eq!(ka2, ka);
+    }
+}
@@ -5,15 +5,53 @@ use std::fmt;
 use serde::de::{self, Deserialize, Visitor};
 use serde::ser::{Error as _, Serialize, SerializeSeq as _, Serializer};
 
-use crate::base64::Bytes;
+use crate::base64::{self, Bytes};
 use crate::error::Error;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 enum OneNonce {
     String(String),
     Bytes(Bytes),
 }
 
+impl OneNonce {
+    fn normalized(&self) -> OneNonce {
+        match self {
+            OneNonce::String(v) if looks_like_base64(v) => match base64::decode_str(v) {
+                Ok(decoded) => {
+                    let enc = base64::encode_str(&decoded);
+                    // dropping the padding must not take the nonce below the minimum length
+                    if enc.len() >= 8 {
+                        OneNonce::String(enc)
+                    } else {
+                        OneNonce::String(v.clone())
+                    }
+                }
+                Err(_) => OneNonce::String(v.clone()),
+            },
+            OneNonce::String(v) => OneNonce::String(v.clone()),
+            OneNonce::Bytes(v) => OneNonce::Bytes(v.clone()),
+        }
+    }
+}
+
+// A string is considered to be base64 if it only contains characters from one of the base64
+// alphabets (standard or url-safe, but not a mixture of the two), optionally followed by up to two
+// padding characters.
+fn looks_like_base64(v: &str) -> bool {
+    let body = v.trim_end_matches('=');
+    if v.len() - body.len() > 2 {
+        return false;
+    }
+
+    let is_std = |c: char| c == '+' || c == '/';
+    let is_url = |c: char| c == '-' || c == '_';
+
+    body.chars()
+        .all(|c| c.is_ascii_alphanumeric() || is_std(c) || is_url(c))
+        && !(body.contains(is_std) && body.contains(is_url))
+}
+
 impl TryFrom<&[u8]> for OneNonce {
     type Error = Error;
 
@@ -42,6 +80,20 @@ impl TryFrom<&Vec<u8>> for OneNonce {
     }
 }
 
+impl TryFrom<Vec<u8>> for OneNonce {
+    type Error = Error;
+
+    fn try_from(v: Vec<u8>) -> Result<Self, Error> {
+        if v.len() >= 8 && v.len() <= 64 {
+            Ok(OneNonce::Bytes(Bytes::from(v)))
+        } else {
+            Err(Error::ParseError(
+                "nonce must be between 8 and 64 bytes".to_string(),
+            ))
+        }
+    }
+}
+
 impl TryFrom<&str> for OneNonce {
     type Error = Error;
 
@@ -146,13 +198,59 @@ impl Visitor<'_> for OneNonceVisitor {
 }
 
 /// echoed back by the verifier to provide freshness
-#[derive(Debug, PartialEq)]
+///
+/// A nonce may consist of several values, which are encoded as an array. A single value wrapped in
+/// a one-element array is equivalent to the bare value: it has a `len()` of 1, compares and
+/// formats the same, and is serialized as the bare value.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Nonce(Vec<OneNonce>);
 
 impl Nonce {
     pub fn is_empty(&self) -> bool {
         self.0.len() == 0
     }
+
+    /// Return the number of values in the nonce
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return a copy of the nonce with any base64-encoded string values re-encoded in the
+    /// canonical form (url-safe alphabet, without padding)
+    ///
+    /// A string is treated as base64 if it consists only of characters from either the standard
+    /// or the url-safe alphabet (but not both), optionally followed by up to two `=` padding
+    /// characters, and decodes successfully. Other strings, and byte string values, are left
+    /// untouched, as are strings whose canonical form would be shorter than the minimum nonce
+    /// length. As only the alphabet and padding are changed, this does not alter the decoded
+    /// value of the nonce, so two normalized nonces may be compared directly.
+    pub fn normalized(&self) -> Nonce {
+        Nonce(self.0.iter().map(|on| on.normalized()).collect())
+    }
+
+    // Return the values of the nonce as bytes (string values as their UTF-8 encoding)
+    pub(crate) fn raw_values(&self) -> Vec<&[u8]> {
+        self.0
+            .iter()
+            .map(|on| match on {
+                OneNonce::Bytes(b) => b.as_slice(),
+                OneNonce::String(s) => s.as_bytes(),
+            })
+            .collect()
+    }
+
+    // Return the byte values of the nonce, failing if any of them is a string
+    pub(crate) fn byte_values(&self) -> Result<Vec<&[u8]>, Error> {
+        self.0
+            .iter()
+            .map(|on| match on {
+                OneNonce::Bytes(b) => Ok(b.as_slice()),
+                OneNonce::String(_) => Err(Error::FormatError(
+                    "nonce contains a string value".to_string(),
+                )),
+            })
+            .collect()
+    }
 }
 
 impl TryFrom<&[u8]> for Nonce {
@@ -163,6 +261,22 @@ impl TryFrom<&[u8]> for Nonce {
     }
 }
 
+impl TryFrom<Vec<u8>> for Nonce {
+    type Error = Error;
+
+    fn try_from(v: Vec<u8>) -> Result<Self, Error> {
+        Ok(Nonce(vec![OneNonce::try_from(v)?]))
+    }
+}
+
+impl<const N: usize> TryFrom<[u8; N]> for Nonce {
+    type Error = Error;
+
+    fn try_from(v: [u8; N]) -> Result<Self, Error> {
+        Nonce::try_from(v.to_vec())
+    }
+}
+
 impl TryFrom<&str> for Nonce {
     type Error = Error;
 
@@ -314,6 +428,9 @@ impl<'de> Visitor<'de> for NonceVisitor {
             n.0.push(v);
         }
 
+        // a one-element array needs no special handling, as a Nonce with a single value is
+        // exactly what decoding the bare value produces
+
         Ok(n)
     }
 }
@@ -323,6 +440,33 @@ mod test {
     use super::*;
     use ciborium::{de::from_reader, ser::into_writer};
 
+    #[test]
+    fn normalized() {
+        let n = Nonce::try_from("+/+/q83vASM=").unwrap();
+        assert_eq!(n.normalized(), "-_-_q83vASM");
+
+        let n = Nonce::try_from("-_-_q83vASM").unwrap();
+        assert_eq!(n.normalized(), n);
+
+        // not base64
+        let n = Nonce::try_from("test value").unwrap();
+        assert_eq!(n.normalized(), n);
+
+        // mixes alphabets
+        let n = Nonce::try_from("+/-_q83vASM").unwrap();
+        assert_eq!(n.normalized(), n);
+
+        // would become too short without padding
+        let n = Nonce::try_from("q83vAQ==").unwrap();
+        assert_eq!(n.normalized(), n);
+
+        let n = Nonce::try_from(["+/+/q83vASM=", "not base64!"].as_slice()).unwrap();
+        assert_eq!(n.normalized().to_string(), "[-_-_q83vASM, not base64!]");
+
+        let n = Nonce::try_from([0xab; 8].as_slice()).unwrap();
+        assert_eq!(n.normalized(), n);
+    }
+
     #[test]
     fn from_str() {
         let n = Nonce::try_from("test value").unwrap();
@@ -373,6 +517,27 @@ mod test {
         );
     }
 
+    #[test]
+    fn from_owned_bytes() {
+        let n = Nonce::try_from(vec![0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef]).unwrap();
+        assert_eq!(n.to_string(), "deadbeefdeadbeef");
+
+        let e = Nonce::try_from(vec![0xde, 0xad, 0xbe, 0xef]).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "parse error: nonce must be between 8 and 64 bytes"
+        );
+
+        let n = Nonce::try_from([0xab_u8; 16]).unwrap();
+        assert_eq!(n.to_string(), "abababababababababababababababab");
+
+        let e = Nonce::try_from([0xab_u8; 65]).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "parse error: nonce must be between 8 and 64 bytes"
+        );
+    }
+
     #[test]
     fn from_str_slice() {
         let n = Nonce::try_from(["test value one", "test value two"].as_slice()).unwrap();
@@ -439,6 +604,48 @@ mod test {
         assert!(!n.is_empty());
     }
 
+    #[test]
+    fn single_element_array() {
+        let bytes = [0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef];
+
+        let bare: Nonce = from_reader(
+            [
+                0x48, // byte string (8)
+                0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef,
+            ]
+            .as_slice(),
+        )
+        .unwrap();
+        let wrapped: Nonce = from_reader(
+            [
+                0x81, // array (1)
+                0x48, // byte string (8)
+                0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef,
+            ]
+            .as_slice(),
+        )
+        .unwrap();
+
+        assert_eq!(bare, wrapped);
+        assert_eq!(wrapped.len(), 1);
+        assert_eq!(bare.len(), 1);
+        assert_eq!(wrapped, bytes.as_slice());
+        assert_eq!(wrapped.to_string(), bare.to_string());
+
+        let mut buf: Vec<u8> = Vec::new();
+        into_writer(&wrapped, &mut buf).unwrap();
+        assert_eq!(buf[0], 0x48);
+
+        let bare: Nonce = serde_json::from_str(r#""test value""#).unwrap();
+        let wrapped: Nonce = serde_json::from_str(r#"["test value"]"#).unwrap();
+
+        assert_eq!(bare, wrapped);
+        assert_eq!(wrapped.len(), 1);
+        assert_eq!(wrapped, "test value");
+        assert_eq!(wrapped.to_string(), "test value");
+        assert_eq!(serde_json::to_string(&wrapped).unwrap(), r#""test value""#);
+    }
+
     #[test]
     fn serde() {
         let bytes = vec![0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef];
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+
+use serde::de::{self, Deserialize, Unexpected, Visitor};
+
+/// An integer that is tolerant of being encoded as a float
+///
+/// Some producers (notably, JavaScript ones) do not distinguish between integers and floats, and
+/// so may write an integer field as, e.g., `1666529184.0`. This accepts such values, so long as
+/// they are integral; floats with a fractional part are rejected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Int(pub i64);
+
+impl<'de> Deserialize<'de> for Int {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(IntVisitor)
+    }
+}
+
+struct IntVisitor;
+
+impl Visitor<'_> for IntVisitor {
+    type Value = Int;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an integer (or an integral float)")
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Int(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        i64::try_from(v)
+            .map(Int)
+            .map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        // note: i64::MAX as f64 rounds up to 2^63, which is out of range, hence the strict
+        // comparison for the upper bound.
+        if v.fract() != 0.0 || v < i64::MIN as f64 || v >= i64::MAX as f64 {
+            return Err(E::invalid_value(Unexpected::Float(v), &self));
+        }
+
+        Ok(Int(v as i64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let v: Int = serde_json::from_str("42").unwrap();
+        assert_eq!(v, Int(42));
+
+        let v: Int = serde_json::from_str("-42.0").unwrap();
+        assert_eq!(v, Int(-42));
+
+        let err = serde_json::from_str::<Int>("42.5").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: floating point `42.5`, expected an integer (or an integral float) \
+             at line 1 column 4"
+        );
+
+        let err = serde_json::from_str::<Int>("1e300").unwrap_err();
+        assert!(err.to_string().starts_with("invalid value: floating point"));
+
+        let err = serde_json::from_str::<Int>(r#""42""#).unwrap_err();
+        assert!(err.to_string().starts_with("invalid type: string"));
+    }
+}
@@ -5,6 +5,14 @@
 // - tags are stripped when serializing to JSON
 // - byte strings are written as base64-encoded strings to JSON (meaning they deserialize as
 //   text strings, losing their original type).
+// - CBOR simple values other than false, true, null and undefined (e.g. simple(16)) cannot be
+//   written to JSON, and only survive CBOR round trips through the crate's own entry points (see
+//   RawValue::Simple).
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
 use serde::de::{self, Deserialize, EnumAccess, MapAccess, SeqAccess, Visitor};
 use serde::ser::{Serialize, Serializer};
 use serde::ser::{SerializeMap as _, SerializeSeq as _, SerializeTupleVariant as _};
@@ -13,7 +21,13 @@ use crate::base64::Bytes;
 use crate::error::Error;
 
 /// deserialized raw JSON object or CBOR map
-#[derive(Debug, PartialEq, Clone)]
+///
+/// `RawValue` implements `Eq`, `Ord` and `Hash` (so that it may be used in sets and as a map key).
+/// Floats are compared using [`f64::total_cmp`], which means that, unlike with `f64`, `NaN` is
+/// equal to itself (provided it has the same bit pattern), and `-0.0` is _not_ equal to `0.0`.
+/// Values of different kinds are ordered in the order in which the kinds are declared
+/// (`Null` < `Integer` < `Bytes` < ... < `Tagged`).
+#[derive(Debug, Clone)]
 pub enum RawValue {
     Null,
     Integer(i64),
@@ -24,6 +38,14 @@ pub enum RawValue {
     Array(Vec<RawValue>),
     Map(Vec<(RawValue, RawValue)>),
     Tagged(u64, Box<RawValue>),
+    /// A CBOR simple value other than false, true, null and undefined, i.e. one of the unassigned
+    /// values 0-19 and 32-255
+    ///
+    /// ciborium does not support these, so they are only encoded and decoded as CBOR simple values
+    /// by the crate's own entry points (e.g. [`RawValue::from_cbor`], [`RawValue::to_cbor`], and
+    /// the COSE methods of [`Ear`](crate::Ear)). Through serde, they are carried as a tag that RFC
+    /// 8949 reserves as invalid (2^64-1). Serializing one to JSON is an error.
+    Simple(u8),
 }
 
 /// specifies the type of a RawValue (without requiring a concrete value)
@@ -38,6 +60,7 @@ pub enum RawValueKind {
     Array,
     Map,
     Tagged,
+    Simple,
 }
 
 impl RawValue {
@@ -52,6 +75,7 @@ impl RawValue {
             RawValue::Array(_) => RawValueKind::Array,
             RawValue::Map(_) => RawValueKind::Map,
             RawValue::Tagged(_, _) => RawValueKind::Tagged,
+            RawValue::Simple(_) => RawValueKind::Simple,
         }
     }
 
@@ -59,6 +83,170 @@ impl RawValue {
         self.kind() == *kind
     }
 
+    /// Return the contained string, if this is a `RawValue::String`
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            RawValue::String(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Return the contained bytes, if this is a `RawValue::Bytes`
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            RawValue::Bytes(b) => Some(b.as_slice()),
+            _ => None,
+        }
+    }
+
+    /// Return the contained integer, if this is a `RawValue::Integer`
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            RawValue::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Return the contained boolean, if this is a `RawValue::Bool`
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            RawValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Decode a value from CBOR, including any simple values (see [`RawValue::Simple`])
+    pub fn from_cbor(cbor: &[u8]) -> Result<RawValue, Error> {
+        ciborium::de::from_reader(simple_values_to_tags(cbor)?.as_ref())
+            .map_err(|e| Error::ParseError(e.to_string()))
+    }
+
+    /// Encode the value as CBOR, including any simple values (see [`RawValue::Simple`])
+    pub fn to_cbor(&self) -> Result<Vec<u8>, Error> {
+        let mut buf: Vec<u8> = Vec::new();
+        ciborium::ser::into_writer(self, &mut buf)
+            .map_err(|e| Error::FormatError(e.to_string()))?;
+
+        Ok(tags_to_simple_values(buf))
+    }
+
+    /// Encode the value as deterministic CBOR
+    ///
+    /// The encoding follows the "Core Deterministic Encoding Requirements" of
+    /// <https://www.rfc-editor.org/rfc/rfc8949#section-4.2.1>: integers, lengths, and tags use
+    /// their shortest form, floats use the shortest form that preserves their value, all lengths
+    /// are definite, and map entries are sorted by the bytewise lexicographic order of their
+    /// encoded keys. This makes the encoding suitable for hashing or comparing values across
+    /// systems. (The regular `Serialize` implementation is unaffected.)
+    pub fn to_canonical_cbor(&self) -> Vec<u8> {
+        let mut buf: Vec<u8> = Vec::new();
+        self.write_canonical_cbor(&mut buf);
+        buf
+    }
+
+    fn write_canonical_cbor(&self, buf: &mut Vec<u8>) {
+        match self {
+            RawValue::Null => buf.push(0xf6),
+            RawValue::Bool(false) => buf.push(0xf4),
+            RawValue::Bool(true) => buf.push(0xf5),
+            RawValue::Integer(i) => {
+                if *i >= 0 {
+                    write_cbor_head(buf, 0, *i as u64)
+                } else {
+                    // -1 - i, without overflowing for i64::MIN
+                    write_cbor_head(buf, 1, !(*i as u64))
+                }
+            }
+            RawValue::Bytes(b) => {
+                write_cbor_head(buf, 2, b.as_slice().len() as u64);
+                buf.extend_from_slice(b.as_slice());
+            }
+            RawValue::String(s) => {
+                write_cbor_head(buf, 3, s.len() as u64);
+                buf.extend_from_slice(s.as_bytes());
+            }
+            RawValue::Array(vs) => {
+                write_cbor_head(buf, 4, vs.len() as u64);
+                for v in vs.iter() {
+                    v.write_canonical_cbor(buf);
+                }
+            }
+            RawValue::Map(vs) => {
+                let mut entries: Vec<(Vec<u8>, Vec<u8>)> = vs
+                    .iter()
+                    .map(|(k, v)| (k.to_canonical_cbor(), v.to_canonical_cbor()))
+                    .collect();
+                entries.sort();
+
+                write_cbor_head(buf, 5, entries.len() as u64);
+                for (k, v) in entries {
+                    buf.extend(k);
+                    buf.extend(v);
+                }
+            }
+            RawValue::Tagged(t, v) => {
+                write_cbor_head(buf, 6, *t);
+                v.write_canonical_cbor(buf);
+            }
+            RawValue::Float(f) => write_cbor_float(buf, *f),
+            RawValue::Simple(v) => write_cbor_head(buf, 7, *v as u64),
+        }
+    }
+
+    /// Render the value in CBOR extended diagnostic notation (RFC 8610, appendix G)
+    ///
+    /// e.g. `1({"a": h'deadbeef', 2: [true, null]})`. This is intended for logging and debugging,
+    /// being considerably more compact than the `Debug` representation.
+    pub fn to_diag(&self) -> String {
+        let mut out = String::new();
+        self.write_diag(&mut out);
+        out
+    }
+
+    fn write_diag(&self, out: &mut String) {
+        match self {
+            RawValue::Null => out.push_str("null"),
+            RawValue::Bool(b) => out.push_str(&b.to_string()),
+            RawValue::Integer(i) => out.push_str(&i.to_string()),
+            RawValue::Bytes(b) => out.push_str(&format!("h'{}'", hex::encode(b.as_slice()))),
+            // JSON string escaping is valid in diagnostic notation
+            RawValue::String(s) => out.push_str(&serde_json::Value::from(s.as_str()).to_string()),
+            RawValue::Float(f) if f.is_nan() => out.push_str("NaN"),
+            RawValue::Float(f) if f.is_infinite() => {
+                out.push_str(if *f > 0.0 { "Infinity" } else { "-Infinity" })
+            }
+            RawValue::Float(f) => out.push_str(&format!("{f:?}")),
+            RawValue::Array(vs) => {
+                out.push('[');
+                for (i, v) in vs.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    v.write_diag(out);
+                }
+                out.push(']');
+            }
+            RawValue::Map(vs) => {
+                out.push('{');
+                for (i, (k, v)) in vs.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(", ");
+                    }
+                    k.write_diag(out);
+                    out.push_str(": ");
+                    v.write_diag(out);
+                }
+                out.push('}');
+            }
+            RawValue::Tagged(t, v) => {
+                out.push_str(&format!("{t}("));
+                v.write_diag(out);
+                out.push(')');
+            }
+            RawValue::Simple(v) => out.push_str(&format!("simple({v})")),
+        }
+    }
+
     pub fn can_convert(&self, kind: &RawValueKind) -> bool {
         matches!(
             (self.kind(), kind),
@@ -82,6 +270,401 @@ impl RawValue {
     }
 }
 
+// Write the head of a CBOR data item of the specified major type, using the shortest form of the
+// argument.
+fn write_cbor_head(buf: &mut Vec<u8>, major: u8, arg: u64) {
+    let major = major << 5;
+
+    if arg < 24 {
+        buf.push(major | arg as u8);
+    } else if arg <= u8::MAX as u64 {
+        buf.push(major | 24);
+        buf.push(arg as u8);
+    } else if arg <= u16::MAX as u64 {
+        buf.push(major | 25);
+        buf.extend_from_slice(&(arg as u16).to_be_bytes());
+    } else if arg <= u32::MAX as u64 {
+        buf.push(major | 26);
+        buf.extend_from_slice(&(arg as u32).to_be_bytes());
+    } else {
+        buf.push(major | 27);
+        buf.extend_from_slice(&arg.to_be_bytes());
+    }
+}
+
+// The tag under which CBOR simple values are carried through serde (see RawValue::Simple). RFC
+// 8949 (section 9.2) reserves it as invalid, so it cannot clash with a tag in well-formed data.
+const SIMPLE_VALUE_TAG: u64 = u64::MAX;
+
+// Whether a simple value is one of the unassigned ones represented by RawValue::Simple (20-23
+// are false, true, null and undefined, and 24-31 are not well-formed).
+fn is_unassigned_simple(v: u64) -> bool {
+    v < 20 || (32..=255).contains(&v)
+}
+
+// Read the head of the CBOR data item at the start of buf, returning its major type, additional
+// information, argument, and length. None is returned if the head is truncated or malformed.
+fn read_cbor_head(buf: &[u8]) -> Option<(u8, u8, u64, usize)> {
+    let first = *buf.first()?;
+    let (major, info) = (first >> 5, first & 0x1f);
+
+    let size = match info {
+        0..=23 | 31 => 0,
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => return None,
+    };
+
+    let arg = match size {
+        0 if info == 31 => 0,
+        0 => info as u64,
+        _ => buf
+            .get(1..=size)?
+            .iter()
+            .fold(0u64, |acc, b| (acc << 8) | *b as u64),
+    };
+
+    Some((major, info, arg, 1 + size))
+}
+
+// Replace the unassigned simple values in a CBOR encoding (which ciborium would reject) with
+// their serde representation (see RawValue::Simple). A malformed encoding is returned unchanged,
+// for the decoder to report.
+pub(crate) fn simple_values_to_tags(cbor: &[u8]) -> Result<Cow<'_, [u8]>, Error> {
+    let mut out: Option<Vec<u8>> = None;
+    let mut pos = 0;
+
+    while pos < cbor.len() {
+        let Some((major, info, arg, len)) = read_cbor_head(&cbor[pos..]) else {
+            return Ok(Cow::Borrowed(cbor));
+        };
+        let start = pos;
+        pos += len;
+
+        match major {
+            // skip the content of definite-length byte and text strings
+            2 | 3 if info != 31 => match usize::try_from(arg).ok().and_then(|n| pos.checked_add(n))
+            {
+                Some(end) if end <= cbor.len() => pos = end,
+                _ => return Ok(Cow::Borrowed(cbor)),
+            },
+            6 if arg == SIMPLE_VALUE_TAG => {
+                return Err(Error::ParseError(format!(
+                    "tag {SIMPLE_VALUE_TAG} is reserved"
+                )))
+            }
+            7 if info <= 24 && is_unassigned_simple(arg) => {
+                let out = out.get_or_insert_with(|| cbor[..start].to_vec());
+                write_cbor_head(out, 6, SIMPLE_VALUE_TAG);
+                write_cbor_head(out, 0, arg);
+                continue;
+            }
+            _ => (),
+        }
+
+        if let Some(out) = out.as_mut() {
+            out.extend_from_slice(&cbor[start..pos]);
+        }
+    }
+
+    Ok(out.map_or(Cow::Borrowed(cbor), Cow::Owned))
+}
+
+// The reverse of simple_values_to_tags(): replace the serde representation of simple values in a
+// CBOR encoding produced by ciborium with the simple values themselves.
+pub(crate) fn tags_to_simple_values(cbor: Vec<u8>) -> Vec<u8> {
+    let mut out: Option<Vec<u8>> = None;
+    let mut pos = 0;
+
+    while pos < cbor.len() {
+        let Some((major, info, arg, len)) = read_cbor_head(&cbor[pos..]) else {
+            break;
+        };
+        let start = pos;
+        pos += len;
+
+        match major {
+            2 | 3 if info != 31 => pos = pos.saturating_add(arg as usize).min(cbor.len()),
+            6 if arg == SIMPLE_VALUE_TAG => {
+                if let Some((0, _, v, vlen)) = read_cbor_head(&cbor[pos..]) {
+                    let out = out.get_or_insert_with(|| cbor[..start].to_vec());
+                    write_cbor_head(out, 7, v);
+                    pos += vlen;
+                    continue;
+                }
+            }
+            _ => (),
+        }
+
+        if let Some(out) = out.as_mut() {
+            out.extend_from_slice(&cbor[start..pos]);
+        }
+    }
+
+    out.unwrap_or(cbor)
+}
+
+// Write a float using the shortest of the half-, single-, and double-precision forms that
+// preserves its value. NaN is written as the canonical half-precision quiet NaN.
+fn write_cbor_float(buf: &mut Vec<u8>, f: f64) {
+    if f.is_nan() {
+        buf.extend_from_slice(&[0xf9, 0x7e, 0x00]);
+        return;
+    }
+
+    let single = f as f32;
+    if single as f64 != f {
+        buf.push(0xfb);
+        buf.extend_from_slice(&f.to_be_bytes());
+        return;
+    }
+
+    match f32_to_f16_exact(single) {
+        Some(half) => {
+            buf.push(0xf9);
+            buf.extend_from_slice(&half.to_be_bytes());
+        }
+        None => {
+            buf.push(0xfa);
+            buf.extend_from_slice(&single.to_be_bytes());
+        }
+    }
+}
+
+// Convert a (non-NaN) single-precision float into the bits of a half-precision float, if that
+// can be done without loss of precision.
+fn f32_to_f16_exact(f: f32) -> Option<u16> {
+    let bits = f.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x007f_ffff;
+
+    if exp == 0xff {
+        // infinity (NaN is handled by the caller)
+        return Some(sign | 0x7c00);
+    }
+
+    if exp == 0 {
+        // zero; single-precision subnormals are too small for half-precision
+        return if mant == 0 { Some(sign) } else { None };
+    }
+
+    let e = exp - 127;
+    if (-14..=15).contains(&e) {
+        // normal
+        if mant & 0x1fff != 0 {
+            return None;
+        }
+        return Some(sign | (((e + 15) as u16) << 10) | (mant >> 13) as u16);
+    }
+
+    if (-24..-14).contains(&e) {
+        // subnormal: the value is (1.mant * 2^e), represented as (m * 2^-24)
+        let full = mant | 0x0080_0000;
+        let shift = -(e + 1);
+        if full & ((1 << shift) - 1) != 0 {
+            return None;
+        }
+        return Some(sign | (full >> shift) as u16);
+    }
+
+    None
+}
+
+impl From<bool> for RawValue {
+    fn from(v: bool) -> Self {
+        RawValue::Bool(v)
+    }
+}
+
+impl From<i64> for RawValue {
+    fn from(v: i64) -> Self {
+        RawValue::Integer(v)
+    }
+}
+
+impl From<i32> for RawValue {
+    fn from(v: i32) -> Self {
+        RawValue::Integer(v.into())
+    }
+}
+
+impl From<f64> for RawValue {
+    fn from(v: f64) -> Self {
+        RawValue::Float(v)
+    }
+}
+
+impl From<&str> for RawValue {
+    fn from(v: &str) -> Self {
+        RawValue::String(v.to_string())
+    }
+}
+
+impl From<String> for RawValue {
+    fn from(v: String) -> Self {
+        RawValue::String(v)
+    }
+}
+
+impl From<&[u8]> for RawValue {
+    fn from(v: &[u8]) -> Self {
+        RawValue::Bytes(Bytes::from(v))
+    }
+}
+
+impl From<Bytes> for RawValue {
+    fn from(v: Bytes) -> Self {
+        RawValue::Bytes(v)
+    }
+}
+
+// serde_cbor's convention for tags is not understood by ciborium (nor the other way around), so
+// tagged values are converted explicitly, rather than through their Serialize implementation.
+#[cfg(feature = "serde_cbor")]
+impl TryFrom<&RawValue> for serde_cbor::Value {
+    type Error = Error;
+
+    fn try_from(v: &RawValue) -> Result<Self, Error> {
+        use serde_cbor::Value;
+
+        Ok(match v {
+            RawValue::Null => Value::Null,
+            RawValue::Integer(i) => Value::Integer(*i as i128),
+            RawValue::Bytes(b) => Value::Bytes(b.as_slice().to_vec()),
+            RawValue::Float(f) => Value::Float(*f),
+            RawValue::String(s) => Value::Text(s.clone()),
+            RawValue::Bool(b) => Value::Bool(*b),
+            RawValue::Array(vs) => Value::Array(
+                vs.iter()
+                    .map(Value::try_from)
+                    .collect::<Result<_, Error>>()?,
+            ),
+            RawValue::Map(vs) => Value::Map(
+                vs.iter()
+                    .map(|(k, v)| Ok((Value::try_from(k)?, Value::try_from(v)?)))
+                    .collect::<Result<_, Error>>()?,
+            ),
+            RawValue::Tagged(t, v) => Value::Tag(*t, Box::new(Value::try_from(v.as_ref())?)),
+            RawValue::Simple(v) => {
+                return Err(Error::ValueError(format!(
+                    "simple({v}) cannot be converted into a serde_cbor value"
+                )))
+            }
+        })
+    }
+}
+
+#[cfg(feature = "serde_cbor")]
+impl TryFrom<serde_cbor::Value> for RawValue {
+    type Error = Error;
+
+    fn try_from(v: serde_cbor::Value) -> Result<Self, Error> {
+        use serde_cbor::Value;
+
+        Ok(match v {
+            Value::Null => RawValue::Null,
+            Value::Integer(i) => RawValue::Integer(
+                i64::try_from(i)
+                    .map_err(|_| Error::ValueError(format!("integer {i} is out of range")))?,
+            ),
+            Value::Bytes(b) => RawValue::Bytes(Bytes::from(b)),
+            Value::Float(f) => RawValue::Float(f),
+            Value::Text(s) => RawValue::String(s),
+            Value::Bool(b) => RawValue::Bool(b),
+            Value::Array(vs) => RawValue::Array(
+                vs.into_iter()
+                    .map(RawValue::try_from)
+                    .collect::<Result<_, Error>>()?,
+            ),
+            Value::Map(vs) => RawValue::Map(
+                vs.into_iter()
+                    .map(|(k, v)| Ok((RawValue::try_from(k)?, RawValue::try_from(v)?)))
+                    .collect::<Result<_, Error>>()?,
+            ),
+            Value::Tag(t, v) => RawValue::Tagged(t, Box::new(RawValue::try_from(*v)?)),
+            v => return Err(Error::ValueError(format!("unsupported value: {v:?}"))),
+        })
+    }
+}
+
+impl RawValue {
+    // position of the value's kind in the declaration order, used to order values of different
+    // kinds
+    fn rank(&self) -> u8 {
+        match self {
+            RawValue::Null => 0,
+            RawValue::Integer(_) => 1,
+            RawValue::Bytes(_) => 2,
+            RawValue::Float(_) => 3,
+            RawValue::String(_) => 4,
+            RawValue::Bool(_) => 5,
+            RawValue::Array(_) => 6,
+            RawValue::Map(_) => 7,
+            RawValue::Tagged(_, _) => 8,
+            RawValue::Simple(_) => 9,
+        }
+    }
+}
+
+impl PartialEq for RawValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RawValue {}
+
+impl PartialOrd for RawValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RawValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (RawValue::Null, RawValue::Null) => Ordering::Equal,
+            (RawValue::Integer(a), RawValue::Integer(b)) => a.cmp(b),
+            (RawValue::Bytes(a), RawValue::Bytes(b)) => a.cmp(b),
+            (RawValue::Float(a), RawValue::Float(b)) => a.total_cmp(b),
+            (RawValue::String(a), RawValue::String(b)) => a.cmp(b),
+            (RawValue::Bool(a), RawValue::Bool(b)) => a.cmp(b),
+            (RawValue::Array(a), RawValue::Array(b)) => a.cmp(b),
+            (RawValue::Map(a), RawValue::Map(b)) => a.cmp(b),
+            (RawValue::Tagged(ta, a), RawValue::Tagged(tb, b)) => (ta, a).cmp(&(tb, b)),
+            (RawValue::Simple(a), RawValue::Simple(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl Hash for RawValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+
+        match self {
+            RawValue::Null => (),
+            RawValue::Integer(v) => v.hash(state),
+            RawValue::Bytes(v) => v.hash(state),
+            // consistent with total_cmp(), which only considers floats equal if their bit
+            // patterns are identical
+            RawValue::Float(v) => v.to_bits().hash(state),
+            RawValue::String(v) => v.hash(state),
+            RawValue::Bool(v) => v.hash(state),
+            RawValue::Array(v) => v.hash(state),
+            RawValue::Map(v) => v.hash(state),
+            RawValue::Tagged(t, v) => {
+                t.hash(state);
+                v.hash(state);
+            }
+            RawValue::Simple(v) => v.hash(state),
+        }
+    }
+}
+
 impl Serialize for RawValue {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -122,6 +705,20 @@ impl Serialize for RawValue {
                     acc.end()
                 }
             }
+            Self::Simple(v) => {
+                if serializer.is_human_readable() {
+                    Err(serde::ser::Error::custom(format!(
+                        "simple({v}) cannot be represented in JSON"
+                    )))
+                } else if !is_unassigned_simple(*v as u64) {
+                    Err(serde::ser::Error::custom(format!(
+                        "simple({v}) is not an unassigned simple value"
+                    )))
+                } else {
+                    RawValue::Tagged(SIMPLE_VALUE_TAG, Box::new(RawValue::Integer(*v as i64)))
+                        .serialize(serializer)
+                }
+            }
         }
     }
 }
@@ -176,8 +773,26 @@ impl<'de> Visitor<'de> for RawValueVisitor {
         Ok(RawValue::Integer(v.into()))
     }
 
+    // Integers are represented as i64, which is sufficient for timestamps, counters, etc. Values
+    // outside of that range (which are possible in both JSON and CBOR) are rejected explicitly,
+    // rather than being silently narrowed.
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        i64::try_from(v)
+            .map(RawValue::Integer)
+            .map_err(|_| E::custom(format!("integer {v} is out of range for i64")))
+    }
+
     fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
-        Ok(RawValue::Integer(v.try_into().map_err(E::custom)?))
+        i64::try_from(v)
+            .map(RawValue::Integer)
+            .map_err(|_| E::custom(format!("integer {v} is out of range for i64")))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        i64::try_from(v)
+            .map(RawValue::Integer)
+            .map_err(|_| E::custom(format!("integer {v} is out of range for i64")))
     }
 
     fn visit_f32<E: de::Error>(self, v: f32) -> Result<Self::Value, E> {
@@ -220,6 +835,22 @@ impl<'de> Visitor<'de> for RawValueVisitor {
         Ok(RawValue::Map(ret))
     }
 
+    // serde_cbor presents tagged values as newtype structs, making the tag available while the
+    // inner value is deserialized.
+    #[cfg(feature = "serde_cbor")]
+    fn visit_newtype_struct<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        let tag = serde_cbor::tags::current_cbor_tag();
+        let val = RawValue::deserialize(deserializer)?;
+
+        match tag {
+            Some(t) => Ok(RawValue::Tagged(t, Box::new(val))),
+            None => Ok(val),
+        }
+    }
+
     // adapted from ciborium implementation of Value::Tag.
     fn visit_enum<A: EnumAccess<'de>>(self, acc: A) -> Result<Self::Value, A::Error> {
         use serde::de::VariantAccess;
@@ -241,7 +872,17 @@ impl<'de> Visitor<'de> for RawValueVisitor {
                 let val = acc
                     .next_element()?
                     .ok_or_else(|| de::Error::custom("expected val"))?;
-                Ok(RawValue::Tagged(tag, Box::new(val)))
+
+                if tag != SIMPLE_VALUE_TAG {
+                    return Ok(RawValue::Tagged(tag, Box::new(val)));
+                }
+
+                match val {
+                    RawValue::Integer(v) if is_unassigned_simple(v as u64) => {
+                        Ok(RawValue::Simple(v as u8))
+                    }
+                    v => Err(de::Error::custom(format!("invalid simple value: {v:?}"))),
+                }
             }
         }
 
@@ -251,10 +892,333 @@ impl<'de> Visitor<'de> for RawValueVisitor {
     }
 }
 
+/// key of a map of raw values, such as annotated evidence or policy claims
+///
+/// CBOR maps may be keyed on integers as well as text strings. As JSON object keys must be
+/// strings, integer keys are stringified when writing JSON (and so are read back as text keys).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MapKey {
+    Integer(i64),
+    Text(String),
+}
+
+impl MapKey {
+    /// Return the contained string, if this is a `MapKey::Text`
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            MapKey::Text(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Return the contained integer, if this is a `MapKey::Integer`
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            MapKey::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for MapKey {
+    fn from(v: &str) -> Self {
+        MapKey::Text(v.to_string())
+    }
+}
+
+impl From<String> for MapKey {
+    fn from(v: String) -> Self {
+        MapKey::Text(v)
+    }
+}
+
+impl From<i64> for MapKey {
+    fn from(v: i64) -> Self {
+        MapKey::Integer(v)
+    }
+}
+
+impl From<&MapKey> for MapKey {
+    fn from(v: &MapKey) -> Self {
+        v.clone()
+    }
+}
+
+impl fmt::Display for MapKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapKey::Integer(i) => write!(f, "{i}"),
+            MapKey::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+impl PartialEq<&str> for MapKey {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+impl PartialEq<i64> for MapKey {
+    fn eq(&self, other: &i64) -> bool {
+        self.as_i64() == Some(*other)
+    }
+}
+
+impl Serialize for MapKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            // serde_json stringifies integer map keys
+            Self::Integer(i) => serializer.serialize_i64(*i),
+            Self::Text(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MapKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(MapKeyVisitor {})
+    }
+}
+
+struct MapKeyVisitor;
+
+impl Visitor<'_> for MapKeyVisitor {
+    type Value = MapKey;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an integer or a text string map key")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(MapKey::Integer(v))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        i64::try_from(v)
+            .map(MapKey::Integer)
+            .map_err(|_| E::custom(format!("integer {v} is out of range for i64")))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        i64::try_from(v)
+            .map(MapKey::Integer)
+            .map_err(|_| E::custom(format!("integer {v} is out of range for i64")))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        i64::try_from(v)
+            .map(MapKey::Integer)
+            .map_err(|_| E::custom(format!("integer {v} is out of range for i64")))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(MapKey::Text(v.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use ciborium::{de::from_reader, ser::into_writer};
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn total_order() {
+        let values = vec![
+            RawValue::Float(f64::NAN),
+            RawValue::String("foo".to_string()),
+            RawValue::Float(1.5),
+            RawValue::Integer(7),
+            RawValue::Float(f64::NAN),
+            RawValue::Float(-0.0),
+            RawValue::Float(0.0),
+            RawValue::Float(1.5),
+            RawValue::Null,
+        ];
+
+        let set: BTreeSet<RawValue> = values.iter().cloned().collect();
+        let sorted: Vec<RawValue> = set.into_iter().collect();
+        assert_eq!(sorted.len(), 7);
+        assert_eq!(sorted[0], RawValue::Null);
+        assert_eq!(sorted[1], RawValue::Integer(7));
+        assert_eq!(sorted[2], RawValue::Float(-0.0));
+        assert_eq!(sorted[3], RawValue::Float(0.0));
+        assert_eq!(sorted[4], RawValue::Float(1.5));
+        assert!(matches!(sorted[5], RawValue::Float(f) if f.is_nan()));
+        assert_eq!(sorted[6], RawValue::String("foo".to_string()));
+
+        let set: HashSet<RawValue> = values.into_iter().collect();
+        assert_eq!(set.len(), 7);
+        assert!(set.contains(&RawValue::Float(f64::NAN)));
+
+        assert_ne!(RawValue::Float(0.0), RawValue::Float(-0.0));
+        assert_eq!(RawValue::Float(f64::NAN), RawValue::Float(f64::NAN));
+    }
+
+    #[test]
+    fn large_integers() {
+        for v in [9_000_000_000, i64::MAX - 1, i64::MAX, i64::MIN] {
+            let rv = RawValue::Integer(v);
+
+            let mut buf: Vec<u8> = Vec::new();
+            into_writer(&rv, &mut buf).unwrap();
+            let rv2: RawValue = from_reader(buf.as_slice()).unwrap();
+            assert_eq!(rv2, rv);
+
+            let val = serde_json::to_string(&rv).unwrap();
+            let rv2: RawValue = serde_json::from_str(&val).unwrap();
+            assert_eq!(rv2, rv);
+        }
+
+        let buf: Vec<u8> = vec![
+            0x1b, // unsigned int in the next 8 bytes
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, // u64::MAX
+        ];
+        let err = from_reader::<RawValue, _>(buf.as_slice()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"Semantic(None, "integer 18446744073709551615 is out of range for i64")"#
+        );
+
+        let buf: Vec<u8> = vec![
+            0x3b, // negative int in the next 8 bytes
+            0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // i64::MIN - 1
+        ];
+        let err = from_reader::<RawValue, _>(buf.as_slice()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"Semantic(None, "integer -9223372036854775809 is out of range for i64")"#
+        );
+
+        let err = serde_json::from_str::<RawValue>("18446744073709551615").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "integer 18446744073709551615 is out of range for i64 at line 1 column 20"
+        );
+    }
+
+    #[test]
+    fn accessors() {
+        let rv = RawValue::String("foo".to_string());
+        assert_eq!(rv.as_str(), Some("foo"));
+        assert_eq!(rv.as_bytes(), None);
+        assert_eq!(rv.as_i64(), None);
+
+        let rv = RawValue::Bytes(Bytes::from([0xde, 0xad].as_slice()));
+        assert_eq!(rv.as_str(), None);
+        assert_eq!(rv.as_bytes(), Some([0xde, 0xad].as_slice()));
+
+        let rv = RawValue::Integer(-7);
+        assert_eq!(rv.as_i64(), Some(-7));
+        assert_eq!(rv.as_str(), None);
+    }
+
+    #[test]
+    fn to_diag() {
+        let rv = RawValue::Tagged(
+            1,
+            Box::new(RawValue::Map(vec![
+                (
+                    RawValue::String("a\"b".to_string()),
+                    RawValue::Bytes(Bytes::from([0xde, 0xad, 0xbe, 0xef].as_slice())),
+                ),
+                (
+                    RawValue::Integer(-2),
+                    RawValue::Array(vec![
+                        RawValue::Bool(true),
+                        RawValue::Null,
+                        RawValue::Float(1.5),
+                        RawValue::Float(f64::NEG_INFINITY),
+                    ]),
+                ),
+            ])),
+        );
+
+        assert_eq!(
+            rv.to_diag(),
+            r#"1({"a\"b": h'deadbeef', -2: [true, null, 1.5, -Infinity]})"#
+        );
+    }
+
+    #[test]
+    fn canonical_cbor() {
+        let map1 = RawValue::Map(vec![
+            (RawValue::String("b".to_string()), RawValue::Integer(1)),
+            (RawValue::Integer(10), RawValue::Integer(-500)),
+            (
+                RawValue::String("a".to_string()),
+                RawValue::Array(vec![RawValue::Float(1.5), RawValue::Bool(true)]),
+            ),
+        ]);
+        let map2 = RawValue::Map(vec![
+            (
+                RawValue::String("a".to_string()),
+                RawValue::Array(vec![RawValue::Float(1.5), RawValue::Bool(true)]),
+            ),
+            (RawValue::String("b".to_string()), RawValue::Integer(1)),
+            (RawValue::Integer(10), RawValue::Integer(-500)),
+        ]);
+
+        assert_eq!(map1.to_canonical_cbor(), map2.to_canonical_cbor());
+        assert_eq!(
+            map1.to_canonical_cbor(),
+            vec![
+                0xa3, // map (3)
+                0x0a, // 10
+                0x39, 0x01, 0xf3, // -500
+                0x61, 0x61, // "a"
+                0x82, // array (2)
+                0xf9, 0x3e, 0x00, // 1.5 (half-precision)
+                0xf5, // true
+                0x61, 0x62, // "b"
+                0x01, // 1
+            ]
+        );
+
+        // the canonical encoding decodes to the same value
+        let rv: RawValue = from_reader(map1.to_canonical_cbor().as_slice()).unwrap();
+        assert_eq!(rv.to_canonical_cbor(), map1.to_canonical_cbor());
+
+        for (rv, expected) in [
+            (RawValue::Integer(0), vec![0x00]),
+            (RawValue::Integer(24), vec![0x18, 0x18]),
+            (RawValue::Integer(-1), vec![0x20]),
+            (RawValue::Integer(65536), vec![0x1a, 0x00, 0x01, 0x00, 0x00]),
+            (
+                RawValue::Integer(i64::MIN),
+                vec![0x3b, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            ),
+            (RawValue::Float(0.0), vec![0xf9, 0x00, 0x00]),
+            (RawValue::Float(-2.0), vec![0xf9, 0xc0, 0x00]),
+            (
+                RawValue::Float(5.960464477539063e-8),
+                vec![0xf9, 0x00, 0x01],
+            ),
+            (
+                RawValue::Float(100000.0),
+                vec![0xfa, 0x47, 0xc3, 0x50, 0x00],
+            ),
+            (
+                RawValue::Float(1.1),
+                vec![0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a],
+            ),
+            (RawValue::Float(f64::INFINITY), vec![0xf9, 0x7c, 0x00]),
+            (RawValue::Float(f64::NAN), vec![0xf9, 0x7e, 0x00]),
+            (
+                RawValue::Tagged(1, Box::new(RawValue::Integer(1363896240))),
+                vec![0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0],
+            ),
+        ] {
+            assert_eq!(rv.to_canonical_cbor(), expected, "{rv:?}");
+        }
+    }
 
     #[test]
     fn serde() {
@@ -395,4 +1359,99 @@ mod test {
             buf
         );
     }
+
+    #[test]
+    fn simple_value() {
+        // [true, simple(16), h'f0', {"a": simple(255)}], with simple(255) encoded in the one-byte
+        // extension (and the byte string content resembling simple(16))
+        let buf = [0x84, 0xf5, 0xf0, 0x41, 0xf0, 0xa1, 0x61, 0x61, 0xf8, 0xff];
+
+        let rv = RawValue::from_cbor(&buf).unwrap();
+        assert_eq!(
+            rv,
+            RawValue::Array(vec![
+                RawValue::Bool(true),
+                RawValue::Simple(16),
+                RawValue::Bytes(Bytes::from([0xf0].as_slice())),
+                RawValue::Map(vec![(
+                    RawValue::String("a".to_string()),
+                    RawValue::Simple(255)
+                )]),
+            ])
+        );
+        assert_eq!(rv.to_cbor().unwrap(), buf);
+        assert_eq!(rv.to_canonical_cbor(), buf);
+        assert_eq!(
+            rv.to_diag(),
+            r#"[true, simple(16), h'f0', {"a": simple(255)}]"#
+        );
+
+        // ciborium on its own does not support simple values
+        let res: Result<RawValue, _> = from_reader(buf.as_slice());
+        assert!(res.is_err());
+
+        let res = serde_json::to_string(&rv);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "simple(16) cannot be represented in JSON"
+        );
+
+        let res = RawValue::Simple(21).to_cbor();
+        assert!(matches!(res, Err(Error::FormatError(t))
+                if t == r#"Value("simple(21) is not an unassigned simple value")"#));
+
+        // the tag used to carry simple values through serde may not appear in the input
+        let buf = [0xdb, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x10];
+        let res = RawValue::from_cbor(&buf);
+        assert!(matches!(res, Err(Error::ParseError(t))
+                if t == "tag 18446744073709551615 is reserved"));
+    }
+
+    #[cfg(feature = "serde_cbor")]
+    #[test]
+    fn serde_cbor() {
+        let rv = RawValue::Map(vec![
+            (
+                RawValue::Integer(1),
+                RawValue::Bytes(Bytes::from([0xde, 0xad].as_slice())),
+            ),
+            (
+                RawValue::String("t".to_string()),
+                RawValue::Array(vec![RawValue::Float(1.5), RawValue::Null]),
+            ),
+        ]);
+
+        // values without tags are encoded the same way by serde_cbor and ciborium
+        let buf = serde_cbor::to_vec(&rv).unwrap();
+        let mut expected: Vec<u8> = Vec::new();
+        into_writer(&rv, &mut expected).unwrap();
+        assert_eq!(buf, expected);
+
+        let rv2: RawValue = serde_cbor::from_slice(&buf).unwrap();
+        assert_eq!(rv2, rv);
+
+        // tagged values are converted through serde_cbor::Value
+        let rv = RawValue::Tagged(1, Box::new(RawValue::Integer(1363896240)));
+        let val = serde_cbor::Value::try_from(&rv).unwrap();
+        assert_eq!(
+            val,
+            serde_cbor::Value::Tag(1, Box::new(serde_cbor::Value::Integer(1363896240)))
+        );
+
+        let buf = serde_cbor::to_vec(&val).unwrap();
+        assert_eq!(buf, vec![0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0]);
+
+        // ...but may be deserialized directly
+        let rv2: RawValue = serde_cbor::from_slice(&buf).unwrap();
+        assert_eq!(rv2, rv);
+        assert_eq!(RawValue::try_from(val).unwrap(), rv);
+
+        let res = serde_cbor::Value::try_from(&RawValue::Simple(16));
+        assert!(matches!(res, Err(Error::ValueError(t))
+                if t == "simple(16) cannot be converted into a serde_cbor value"));
+
+        let res = RawValue::try_from(serde_cbor::Value::Integer(u64::MAX as i128));
+        assert!(matches!(res, Err(Error::ValueError(t))
+                if t == "integer 18446744073709551615 is out of range"));
+    }
 }
@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::Deref;
+
+use crate::ear::Ear;
+
+/// A validated EAR that can no longer be mutated
+///
+/// A `SealedEar` is produced by [`Ear::seal`], which validates the EAR before wrapping it. The
+/// wrapped EAR may be read (via [`Deref`]) and signed using any of the [`Ear`] signing methods,
+/// but not modified, guaranteeing that the signed token corresponds to a validated structure. Use
+/// [`SealedEar::into_inner`] to recover the `Ear` for further edits (after which it must be sealed
+/// again).
+#[derive(Debug, PartialEq)]
+pub struct SealedEar(Ear);
+
+impl SealedEar {
+    // Only constructed by Ear::seal(), once the EAR has been validated.
+    pub(crate) fn new(ear: Ear) -> SealedEar {
+        SealedEar(ear)
+    }
+
+    /// Unwrap the sealed EAR, returning the underlying (mutable) `Ear`
+    pub fn into_inner(self) -> Ear {
+        self.0
+    }
+}
+
+impl Deref for SealedEar {
+    type Target = Ear;
+
+    fn deref(&self) -> &Ear {
+        &self.0
+    }
+}
+
+impl AsRef<Ear> for SealedEar {
+    fn as_ref(&self) -> &Ear {
+        &self.0
+    }
+}
@@ -0,0 +1,303 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Alternative serde representations of the crate's types
+//!
+//! Each module here provides `serialize` and `deserialize` functions for use with
+//! `#[serde(with = "...")]` on the fields of a user-defined struct, overriding the type's default
+//! representation, e.g.
+//!
+//! ```
+//! use ear::{Bytes, TrustTier};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Record {
+//!     #[serde(with = "ear::serde::bytes_as_hex")]
+//!     digest: Bytes,
+//!     #[serde(with = "ear::serde::trust_tier_as_int")]
+//!     status: TrustTier,
+//! }
+//! ```
+//!
+//! The representations are the same in all formats (e.g. `bytes_as_hex` produces a hex string in
+//! CBOR, as well as in JSON).
+
+/// Represent a [`Bytes`](crate::Bytes) as a hex string
+pub mod bytes_as_hex {
+    use ::serde::{de, Deserialize, Deserializer, Serializer};
+
+    use crate::Bytes;
+
+    pub fn serialize<S>(v: &Bytes, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&hex::encode(v.as_slice()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Bytes, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s).map(Bytes::from).map_err(de::Error::custom)
+    }
+}
+
+/// Represent a [`Bytes`](crate::Bytes) as a base64 string using the standard alphabet, with
+/// padding
+///
+/// Either alphabet is accepted when deserializing, with or without padding.
+pub mod bytes_as_base64_std {
+    use ::base64::{engine::general_purpose, Engine as _};
+    use ::serde::{de, Deserialize, Deserializer, Serializer};
+
+    use crate::Bytes;
+
+    pub fn serialize<S>(v: &Bytes, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&general_purpose::STANDARD.encode(v.as_slice()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Bytes, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        crate::base64::decode_str(&s)
+            .map(Bytes::from)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Represent a [`Nonce`](crate::Nonce) as a hex string (or an array of hex strings, if it has
+/// multiple values)
+///
+/// Only nonces with byte string values may be serialized this way; deserialized values are always
+/// byte strings.
+pub mod nonce_as_hex {
+    use ::serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Nonce;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum HexNonce {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    pub fn serialize<S>(v: &Nonce, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let values: Vec<String> = v
+            .byte_values()
+            .map_err(ser::Error::custom)?
+            .into_iter()
+            .map(hex::encode)
+            .collect();
+
+        match values.as_slice() {
+            [one] => serializer.serialize_str(one),
+            _ => values.serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Nonce, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let values = match HexNonce::deserialize(deserializer)? {
+            HexNonce::One(v) => vec![v],
+            HexNonce::Many(vs) => vs,
+        };
+
+        let values = values
+            .iter()
+            .map(hex::decode)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(de::Error::custom)?;
+
+        Nonce::try_from(values.as_slice()).map_err(de::Error::custom)
+    }
+}
+
+/// Represent a [`TrustTier`](crate::TrustTier) as its integer value
+pub mod trust_tier_as_int {
+    use ::serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::TrustTier;
+
+    pub fn serialize<S>(v: &TrustTier, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i8(v.value())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<TrustTier, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // TrustTier accepts both integers and names in any format
+        TrustTier::deserialize(deserializer)
+    }
+}
+
+/// Represent a [`TrustTier`](crate::TrustTier) as its name (e.g. `"affirming"`)
+pub mod trust_tier_as_str {
+    use ::serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::TrustTier;
+
+    pub fn serialize<S>(v: &TrustTier, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(match v {
+            TrustTier::None => "none",
+            TrustTier::Affirming => "affirming",
+            TrustTier::Warning => "warning",
+            TrustTier::Contraindicated => "contraindicated",
+        })
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<TrustTier, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        TrustTier::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ::serde::{Deserialize, Serialize};
+    use ciborium::{de::from_reader, ser::into_writer};
+
+    use crate::{Bytes, Nonce, TrustTier};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct BytesHex {
+        #[serde(with = "super::bytes_as_hex")]
+        v: Bytes,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct BytesBase64Std {
+        #[serde(with = "super::bytes_as_base64_std")]
+        v: Bytes,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct NonceHex {
+        #[serde(with = "super::nonce_as_hex")]
+        v: Nonce,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct TierInt {
+        #[serde(with = "super::trust_tier_as_int")]
+        v: TrustTier,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct TierStr {
+        #[serde(with = "super::trust_tier_as_str")]
+        v: TrustTier,
+    }
+
+    #[test]
+    fn bytes_as_hex() {
+        let w = BytesHex {
+            v: Bytes::from([0xde, 0xad, 0xbe, 0xef].as_slice()),
+        };
+
+        let val = serde_json::to_string(&w).unwrap();
+        assert_eq!(val, r#"{"v":"deadbeef"}"#);
+        assert_eq!(serde_json::from_str::<BytesHex>(&val).unwrap(), w);
+
+        let res = serde_json::from_str::<BytesHex>(r#"{"v":"not hex"}"#);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn bytes_as_base64_std() {
+        let w = BytesBase64Std {
+            v: Bytes::from([0xfb, 0xff, 0xfe].as_slice()),
+        };
+
+        let val = serde_json::to_string(&w).unwrap();
+        assert_eq!(val, r#"{"v":"+//+"}"#);
+        assert_eq!(serde_json::from_str::<BytesBase64Std>(&val).unwrap(), w);
+
+        // the url-safe alphabet is also accepted
+        let w2: BytesBase64Std = serde_json::from_str(r#"{"v":"-__-"}"#).unwrap();
+        assert_eq!(w2, w);
+    }
+
+    #[test]
+    fn nonce_as_hex() {
+        let w = NonceHex {
+            v: Nonce::try_from([0x01; 8]).unwrap(),
+        };
+
+        let val = serde_json::to_string(&w).unwrap();
+        assert_eq!(val, r#"{"v":"0101010101010101"}"#);
+        assert_eq!(serde_json::from_str::<NonceHex>(&val).unwrap(), w);
+
+        let w = NonceHex {
+            v: Nonce::try_from([vec![0x01; 8], vec![0x02; 8]].as_slice()).unwrap(),
+        };
+
+        let val = serde_json::to_string(&w).unwrap();
+        assert_eq!(val, r#"{"v":["0101010101010101","0202020202020202"]}"#);
+        assert_eq!(serde_json::from_str::<NonceHex>(&val).unwrap(), w);
+
+        // the representation is the same in CBOR
+        let mut buf: Vec<u8> = Vec::new();
+        into_writer(&w, &mut buf).unwrap();
+        assert_eq!(from_reader::<NonceHex, _>(buf.as_slice()).unwrap(), w);
+
+        let w = NonceHex {
+            v: Nonce::try_from("a-string-nonce").unwrap(),
+        };
+        let res = serde_json::to_string(&w);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn trust_tier_as_int() {
+        let w = TierInt {
+            v: TrustTier::Warning,
+        };
+
+        let val = serde_json::to_string(&w).unwrap();
+        assert_eq!(val, r#"{"v":32}"#);
+        assert_eq!(serde_json::from_str::<TierInt>(&val).unwrap(), w);
+    }
+
+    #[test]
+    fn trust_tier_as_str() {
+        let w = TierStr {
+            v: TrustTier::Contraindicated,
+        };
+
+        let mut buf: Vec<u8> = Vec::new();
+        into_writer(&w, &mut buf).unwrap();
+        assert_eq!(
+            buf,
+            vec![
+                0xa1, // map (1)
+                0x61, 0x76, // "v"
+                0x6f, // text string (15)
+                0x63, 0x6f, 0x6e, 0x74, 0x72, 0x61, 0x69, 0x6e, // "contrain"
+                0x64, 0x69, 0x63, 0x61, 0x74, 0x65, 0x64, // "dicated"
+            ]
+        );
+        assert_eq!(from_reader::<TierStr, _>(buf.as_slice()).unwrap(), w);
+    }
+}
@@ -0,0 +1,370 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::value::Value;
+
+use crate::algorithm::Algorithm;
+use crate::error::Error;
+
+const COSE_SIGN_TAG: u64 = 98;
+const COSE_SIGN1_TAG: u64 = 18;
+
+const HEADER_ALG: i64 = 1;
+const HEADER_KID: i64 = 4;
+
+/// Information about one of the signatures on a COSE token
+///
+/// This is extracted from the signature's headers without verifying the signature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureInfo {
+    /// The COSE algorithm identifier of the signature (if specified)
+    ///
+    /// This is only ever taken from the protected header. An `alg` in the unprotected header is
+    /// rejected, unless it matches the protected one (see
+    /// [`Ear::from_cose_jwk`](crate::Ear::from_cose_jwk)).
+    pub alg: Option<i32>,
+    /// The key identifier of the signer (if specified)
+    pub kid: Option<Vec<u8>>,
+}
+
+impl SignatureInfo {
+    /// Return the signature algorithm, if it has been specified and is one of those supported by
+    /// this implementation
+    pub fn algorithm(&self) -> Option<Algorithm> {
+        match self.alg? {
+            cose::algs::ES256 => Some(Algorithm::ES256),
+            cose::algs::ES384 => Some(Algorithm::ES384),
+            cose::algs::ES512 => Some(Algorithm::ES512),
+            cose::algs::EDDSA => Some(Algorithm::EdDSA),
+            cose::algs::PS256 => Some(Algorithm::PS256),
+            cose::algs::PS384 => Some(Algorithm::PS384),
+            cose::algs::PS512 => Some(Algorithm::PS512),
+            #[cfg(feature = "es256k")]
+            cose::algs::ES256K => Some(Algorithm::ES256K),
+            _ => None,
+        }
+    }
+
+    // Extract the info from the protected (encoded) and unprotected headers of a signature, with
+    // the protected header taking precedence. The alg is only trusted from the protected header:
+    // an unprotected alg is an error unless it is the same (as it could otherwise be used to
+    // mislead a verifier as to how the signature is to be checked).
+    fn from_headers(protected: &Value, unprotected: &Value) -> Result<SignatureInfo, Error> {
+        let protected: Value = match protected {
+            Value::Bytes(b) if b.is_empty() => Value::Map(Vec::new()),
+            Value::Bytes(b) => ciborium::de::from_reader(b.as_slice())
+                .map_err(|e| Error::ParseError(format!("protected header: {e}")))?,
+            _ => {
+                return Err(Error::ParseError(
+                    "protected header must be a byte string".to_string(),
+                ))
+            }
+        };
+
+        let (protected, unprotected) = match (protected, unprotected) {
+            (Value::Map(p), Value::Map(u)) => (p, u),
+            _ => return Err(Error::ParseError("headers must be maps".to_string())),
+        };
+
+        fn find_in(bucket: &[(Value, Value)], label: i64) -> Option<&Value> {
+            bucket
+                .iter()
+                .find(|(k, _)| matches!(k, Value::Integer(i) if i128::from(*i) == label.into()))
+                .map(|(_, v)| v)
+        }
+        let find = |label: i64| find_in(&protected, label).or_else(|| find_in(unprotected, label));
+
+        let parse_alg = |v: &Value| match v {
+            Value::Integer(i) => i32::try_from(*i)
+                .map_err(|_| Error::ParseError("alg header out of range".to_string())),
+            _ => Err(Error::ParseError(
+                "alg header must be an integer".to_string(),
+            )),
+        };
+
+        let alg = find_in(&protected, HEADER_ALG).map(parse_alg).transpose()?;
+
+        if let Some(v) = find_in(unprotected, HEADER_ALG) {
+            match (alg, parse_alg(v)?) {
+                (Some(a), u) if a == u => (),
+                (Some(_), _) => {
+                    return Err(Error::VerifyError(
+                        "unprotected alg header conflicts with the protected one".to_string(),
+                    ))
+                }
+                (None, _) => {
+                    return Err(Error::VerifyError(
+                        "alg header must be protected".to_string(),
+                    ))
+                }
+            }
+        }
+
+        let kid = match find(HEADER_KID) {
+            Some(Value::Bytes(b)) => Some(b.clone()),
+            Some(_) => {
+                return Err(Error::ParseError(
+                    "kid header must be a byte string".to_string(),
+                ))
+            }
+            None => None,
+        };
+
+        Ok(SignatureInfo { alg, kid })
+    }
+}
+
+// A signature on a COSE_Sign or COSE_Sign1 token
+pub(crate) struct CoseSignature {
+    pub info: SignatureInfo,
+    // The encoded protected header of the COSE_Signature (None for a COSE_Sign1, which has no
+    // per-signer headers)
+    pub protected: Option<Vec<u8>>,
+    pub signature: Vec<u8>,
+}
+
+// The content of a COSE_Sign or COSE_Sign1 token, and the signatures over it
+pub(crate) struct CoseSigned {
+    // The encoded protected header of the message body
+    pub protected: Vec<u8>,
+    // The payload (None if detached)
+    pub payload: Option<Vec<u8>>,
+    pub signatures: Vec<CoseSignature>,
+}
+
+impl CoseSigned {
+    // Construct the Sig_structure over which the specified signature was computed (assuming empty
+    // external AAD).
+    //
+    // See <https://www.rfc-editor.org/rfc/rfc9052#section-4.4>
+    pub fn to_be_signed(&self, sig: &CoseSignature) -> Result<Vec<u8>, Error> {
+        let payload = self
+            .payload
+            .as_ref()
+            .ok_or_else(|| Error::VerifyError("detached payloads are not supported".to_string()))?;
+
+        let mut items = match &sig.protected {
+            Some(p) => vec![
+                Value::Text("Signature".to_string()),
+                Value::Bytes(self.protected.clone()),
+                Value::Bytes(p.clone()),
+            ],
+            None => vec![
+                Value::Text("Signature1".to_string()),
+                Value::Bytes(self.protected.clone()),
+            ],
+        };
+        items.push(Value::Bytes(Vec::new()));
+        items.push(Value::Bytes(payload.clone()));
+
+        let mut buf: Vec<u8> = Vec::new();
+        ciborium::ser::into_writer(&Value::Array(items), &mut buf)
+            .map_err(|e| Error::FormatError(e.to_string()))?;
+
+        Ok(buf)
+    }
+}
+
+/// Extract information about each of the signatures on a COSE_Sign or COSE_Sign1 token, without
+/// verifying them.
+pub(crate) fn cose_signature_info(token: &[u8]) -> Result<Vec<SignatureInfo>, Error> {
+    Ok(cose_signed(token)?
+        .signatures
+        .into_iter()
+        .map(|s| s.info)
+        .collect())
+}
+
+// Decode a COSE_Sign or COSE_Sign1 token into its content and signatures, without verifying them.
+pub(crate) fn cose_signed(token: &[u8]) -> Result<CoseSigned, Error> {
+    let value: Value =
+        ciborium::de::from_reader(token).map_err(|e| Error::ParseError(e.to_string()))?;
+
+    let (tag, value) = match value {
+        Value::Tag(t, v) if t == COSE_SIGN_TAG || t == COSE_SIGN1_TAG => (Some(t), *v),
+        Value::Tag(t, _) => {
+            return Err(Error::ParseError(format!(
+                "unexpected tag {t} (expected COSE_Sign or COSE_Sign1)"
+            )))
+        }
+        v => (None, v),
+    };
+
+    let mut items = match value {
+        Value::Array(items) if items.len() == 4 => items,
+        _ => {
+            return Err(Error::ParseError(
+                "COSE_Sign and COSE_Sign1 must be an array of 4 items".to_string(),
+            ))
+        }
+    };
+
+    let payload = match &items[2] {
+        Value::Bytes(b) => Some(b.clone()),
+        Value::Null => None,
+        _ => {
+            return Err(Error::ParseError(
+                "payload must be a byte string or nil".to_string(),
+            ))
+        }
+    };
+
+    // COSE_Sign = [protected, unprotected, payload, [+ COSE_Signature]]
+    // COSE_Sign1 = [protected, unprotected, payload, signature]
+    let signatures = match (tag, items.swap_remove(3)) {
+        (Some(COSE_SIGN_TAG) | None, Value::Array(signatures)) => signatures
+            .into_iter()
+            .enumerate()
+            .map(|(i, sig)| match sig {
+                // COSE_Signature = [protected, unprotected, signature]
+                Value::Array(s) if s.len() == 3 => {
+                    let info = SignatureInfo::from_headers(&s[0], &s[1]).map_err(|e| {
+                        let msg = match e {
+                            Error::ParseError(s) => s,
+                            _ => e.to_string(),
+                        };
+                        Error::ParseError(format!("signature {i}: {msg}"))
+                    })?;
+
+                    match (&s[0], &s[2]) {
+                        (Value::Bytes(p), Value::Bytes(sig)) => Ok(CoseSignature {
+                            info,
+                            protected: Some(p.clone()),
+                            signature: sig.clone(),
+                        }),
+                        _ => Err(Error::ParseError(format!(
+                            "signature {i}: signature must be a byte string"
+                        ))),
+                    }
+                }
+                _ => Err(Error::ParseError(format!(
+                    "signature {i}: COSE_Signature must be an array of 3 items"
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        (Some(COSE_SIGN1_TAG) | None, Value::Bytes(sig)) => vec![CoseSignature {
+            info: SignatureInfo::from_headers(&items[0], &items[1])?,
+            protected: None,
+            signature: sig,
+        }],
+        _ => {
+            return Err(Error::ParseError(
+                "signatures must be an array (COSE_Sign) or a byte string (COSE_Sign1)".to_string(),
+            ))
+        }
+    };
+
+    let protected = match &items[0] {
+        Value::Bytes(b) => b.clone(),
+        _ => {
+            return Err(Error::ParseError(
+                "protected header must be a byte string".to_string(),
+            ))
+        }
+    };
+
+    Ok(CoseSigned {
+        protected,
+        payload,
+        signatures,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn encode(value: &Value) -> Vec<u8> {
+        let mut buf: Vec<u8> = Vec::new();
+        ciborium::ser::into_writer(value, &mut buf).unwrap();
+        buf
+    }
+
+    fn alg_header(alg: i32) -> Value {
+        Value::Bytes(encode(&Value::Map(vec![(
+            Value::Integer(1.into()),
+            Value::Integer(alg.into()),
+        )])))
+    }
+
+    fn kid_header(kid: &[u8]) -> Value {
+        Value::Map(vec![(Value::Integer(4.into()), Value::Bytes(kid.to_vec()))])
+    }
+
+    #[test]
+    fn unprotected_alg() {
+        let alg_map =
+            |alg: i32| Value::Map(vec![(Value::Integer(1.into()), Value::Integer(alg.into()))]);
+
+        let info = SignatureInfo::from_headers(
+            &alg_header(cose::algs::ES256),
+            &alg_map(cose::algs::ES256),
+        )
+        .unwrap();
+        assert_eq!(info.algorithm(), Some(Algorithm::ES256));
+
+        let res = SignatureInfo::from_headers(
+            &alg_header(cose::algs::ES256),
+            &alg_map(cose::algs::EDDSA),
+        );
+        assert!(matches!(res, Err(Error::VerifyError(t))
+                if t == "unprotected alg header conflicts with the protected one"));
+
+        let res =
+            SignatureInfo::from_headers(&Value::Bytes(Vec::new()), &alg_map(cose::algs::ES256));
+        assert!(matches!(res, Err(Error::VerifyError(t)) if t == "alg header must be protected"));
+    }
+
+    #[test]
+    fn two_signers() {
+        let token = encode(&Value::Tag(
+            COSE_SIGN_TAG,
+            Box::new(Value::Array(vec![
+                Value::Bytes(Vec::new()),
+                Value::Map(Vec::new()),
+                Value::Bytes(vec![0xa0]),
+                Value::Array(vec![
+                    Value::Array(vec![
+                        alg_header(cose::algs::ES256),
+                        kid_header(b"k1"),
+                        Value::Bytes(vec![0; 64]),
+                    ]),
+                    Value::Array(vec![
+                        alg_header(cose::algs::EDDSA),
+                        kid_header(b"k2"),
+                        Value::Bytes(vec![0; 64]),
+                    ]),
+                ]),
+            ])),
+        ));
+
+        let info = cose_signature_info(&token).unwrap();
+        assert_eq!(info.len(), 2);
+        assert_eq!(info[0].alg, Some(cose::algs::ES256));
+        assert_eq!(info[0].algorithm(), Some(Algorithm::ES256));
+        assert_eq!(info[0].kid, Some(b"k1".to_vec()));
+        assert_eq!(info[1].alg, Some(cose::algs::EDDSA));
+        assert_eq!(info[1].algorithm(), Some(Algorithm::EdDSA));
+        assert_eq!(info[1].kid, Some(b"k2".to_vec()));
+    }
+
+    #[test]
+    fn bad_signature() {
+        let token = encode(&Value::Tag(
+            COSE_SIGN_TAG,
+            Box::new(Value::Array(vec![
+                Value::Bytes(Vec::new()),
+                Value::Map(Vec::new()),
+                Value::Bytes(vec![0xa0]),
+                Value::Array(vec![Value::Array(vec![
+                    Value::Map(Vec::new()),
+                    Value::Map(Vec::new()),
+                    Value::Bytes(vec![0; 64]),
+                ])]),
+            ])),
+        ));
+
+        let res = cose_signature_info(&token);
+        assert!(matches!(res, Err(Error::ParseError(t))
+                if t == "signature 0: protected header must be a byte string"));
+    }
+}
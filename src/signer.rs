@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+
+use crate::algorithm::Algorithm;
+use crate::error::Error;
+
+/// A signer that produces signatures asynchronously
+///
+/// This allows signing using keys held by a remote service (such as a cloud KMS), without
+/// blocking on the request.
+pub trait AsyncSigner {
+    /// Return the algorithm used by the signer
+    fn algorithm(&self) -> Algorithm;
+
+    /// Sign the specified data, returning the raw signature
+    ///
+    /// The signature must be in the form expected by JWS for the signer's algorithm (e.g. the
+    /// concatenation of R and S for ECDSA, rather than a DER-encoded signature).
+    fn sign(&self, data: &[u8]) -> impl Future<Output = Result<Vec<u8>, Error>> + Send;
+}
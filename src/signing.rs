@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+
+use crate::algorithm::Algorithm;
+
+/// A private key used to sign an EAR (see [`Ear::sign`](crate::Ear::sign)), along with the
+/// algorithm it is to be used with
+///
+/// The key material is omitted from the `Debug` output, so that it is not inadvertently logged.
+#[derive(Clone, PartialEq)]
+pub enum SigningKey {
+    /// A PEM-encoded private key
+    Pem { alg: Algorithm, key: Vec<u8> },
+    /// A DER-encoded private key
+    Der { alg: Algorithm, key: Vec<u8> },
+}
+
+impl SigningKey {
+    /// Create a new signing key from a PEM-encoded private key
+    pub fn pem(alg: Algorithm, key: &[u8]) -> SigningKey {
+        SigningKey::Pem {
+            alg,
+            key: key.to_vec(),
+        }
+    }
+
+    /// Create a new signing key from a DER-encoded private key
+    pub fn der(alg: Algorithm, key: &[u8]) -> SigningKey {
+        SigningKey::Der {
+            alg,
+            key: key.to_vec(),
+        }
+    }
+
+    /// Return the algorithm the key is to be used with
+    pub fn alg(&self) -> Algorithm {
+        match self {
+            SigningKey::Pem { alg, .. } | SigningKey::Der { alg, .. } => *alg,
+        }
+    }
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, alg) = match self {
+            SigningKey::Pem { alg, .. } => ("Pem", alg),
+            SigningKey::Der { alg, .. } => ("Der", alg),
+        };
+
+        f.debug_struct(name)
+            .field("alg", alg)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use serde::ser::{Serialize, SerializeMap};
+
+use crate::ear::Ear;
+use crate::id::VerifierID;
+use crate::trust::tier::TrustTier;
+
+/// A compact summary of an EAR
+///
+/// This contains the profile, issue time, and verifier identity of the EAR, along with the status
+/// of each of its submods. Evidence, trustworthiness vectors, and extensions are omitted. This is
+/// intended for reporting to dashboards and monitoring systems; its serialization is kept stable
+/// independently of that of [`Ear`].
+///
+/// This is a report about an EAR, rather than an attestation result in its own right: for the
+/// latter, relying parties should use the AR4SI condensed form (see
+/// [`CondensedResult`](crate::CondensedResult)).
+#[derive(Debug, PartialEq)]
+pub struct EarSummary {
+    /// The EAT profile of the summarized EAR
+    pub profile: String,
+    /// The time at which the summarized EAR was issued
+    pub iat: i64,
+    /// Identifier of the verifier that created the summarized EAR
+    pub vid: VerifierID,
+    /// The overall status of each submod in the summarized EAR
+    pub submods: BTreeMap<String, TrustTier>,
+}
+
+impl From<&Ear> for EarSummary {
+    fn from(ear: &Ear) -> Self {
+        EarSummary {
+            profile: ear.profile.clone(),
+            iat: ear.iat,
+            vid: VerifierID {
+                build: ear.vid.build.clone(),
+                developer: ear.vid.developer.clone(),
+            },
+            submods: ear.submod_statuses(),
+        }
+    }
+}
+
+impl Serialize for EarSummary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(4))?;
+
+        map.serialize_entry("profile", &self.profile)?;
+        map.serialize_entry("iat", &self.iat)?;
+        map.serialize_entry("verifier", &self.vid)?;
+        map.serialize_entry("submods", &self.submods)?;
+
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{claim, Appraisal};
+
+    #[test]
+    fn serialize() {
+        let mut ear = Ear::new();
+        ear.profile = "tag:github.com,2023:veraison/ear".to_string();
+        ear.iat = 1666529184;
+        ear.vid.build = "vsts 0.0.1".to_string();
+        ear.vid.developer = "https://veraison-project.org".to_string();
+        ear.raw_evidence = Some(vec![0xde, 0xad, 0xbe, 0xef].as_slice().into());
+
+        let mut appraisal = Appraisal::new();
+        appraisal.trust_vector.hardware.set(claim::UNSAFE_HARDWARE);
+        appraisal.update_status_from_trust_vector();
+        ear.submods.insert("cpu".to_string(), appraisal);
+        ear.submods.insert("gpu".to_string(), Appraisal::new());
+
+        let summary = ear.summary();
+        assert_eq!(summary.submods["cpu"], TrustTier::Warning);
+
+        let val = serde_json::to_string(&summary).unwrap();
+        assert_eq!(
+            val,
+            r#"{"profile":"tag:github.com,2023:veraison/ear","iat":1666529184,"#.to_owned()
+                + r#""verifier":{"developer":"https://veraison-project.org","build":"vsts 0.0.1"},"#
+                + r#""submods":{"cpu":"warning","gpu":"none"}}"#
+        );
+    }
+}
@@ -1,4 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use crate::error::Error;
 
 use phf::{phf_map, Map};
@@ -6,29 +10,58 @@ use phf::{phf_map, Map};
 use super::tier::TrustTier;
 
 /// Description of a trustworthiness claim
+///
+/// The descriptions of the standard claims are static; descriptions of claims defined at runtime
+/// may own their strings (see [`ClaimDescription::into_static`]).
 #[derive(Debug, Clone)]
-pub struct ClaimDescripiton<'a> {
+pub struct ClaimDescription<'a> {
     /// The key under which the claim is serialized in CBOR
     pub key: i8,
     /// The name under which the claim is serialized in JSON
-    pub name: &'a str,
+    pub name: Cow<'a, str>,
+}
+
+impl ClaimDescription<'_> {
+    /// Convert into a description with a `'static` lifetime, taking ownership of any borrowed
+    /// strings
+    pub fn into_static(self) -> ClaimDescription<'static> {
+        ClaimDescription {
+            key: self.key,
+            name: Cow::Owned(self.name.into_owned()),
+        }
+    }
 }
 
 /// Description of the claim value
 #[derive(Debug, Clone)]
 pub struct ValueDescription<'a> {
     /// String tag given to the claim value
-    pub tag: &'a str,
+    pub tag: Cow<'a, str>,
     /// A short description of the claim value
     ///
     /// This is intended to be used in error messages etc.
-    pub short: &'a str,
+    pub short: Cow<'a, str>,
     /// A longer description of the claim value
     ///
     /// This is a longer explanation of what the value is intended to represent.
-    pub long: &'a str,
+    pub long: Cow<'a, str>,
+}
+
+impl ValueDescription<'_> {
+    /// Convert into a description with a `'static` lifetime, taking ownership of any borrowed
+    /// strings
+    pub fn into_static(self) -> ValueDescription<'static> {
+        ValueDescription {
+            tag: Cow::Owned(self.tag.into_owned()),
+            short: Cow::Owned(self.short.into_owned()),
+            long: Cow::Owned(self.long.into_owned()),
+        }
+    }
 }
 
+// Appended to the descriptions of negative values mirrored from positive ones.
+const MIRRORED_DESC_SUFFIX: &str = " (sourced from attestation)";
+
 pub const VERIFIER_MALFUNCTION: i8 = -1;
 pub const NO_CLAIM: i8 = 0;
 pub const UNEXPECTED_EVIDENCE: i8 = 1;
@@ -40,31 +73,31 @@ pub const CRYPTO_VALIDATION_FAILED: i8 = 99;
 /// Values common to all claims.
 pub static COMMON_CLAIM_MAP: &Map<i8, ValueDescription<'static>> = &phf_map! {
     -1i8 => ValueDescription{
-        tag: "verifier_malfunction",
-        short: "verifier malfunction",
-        long:  "A verifier malfunction occurred during evidence appraisal."
+        tag: Cow::Borrowed("verifier_malfunction"),
+        short: Cow::Borrowed("verifier malfunction"),
+        long:  Cow::Borrowed("A verifier malfunction occurred during evidence appraisal.")
     },
     0i8 => ValueDescription{
-        tag: "no_claim",
-        short: "no claim is being made",
-        long:  "The evidence received is insufficient to make a conclusion."
+        tag: Cow::Borrowed("no_claim"),
+        short: Cow::Borrowed("no claim is being made"),
+        long:  Cow::Borrowed("The evidence received is insufficient to make a conclusion.")
     },
     1i8 => ValueDescription{
-        tag: "unexpected_evidence",
-        short: "unexpected evidence",
-        long:  "The evidence received contains unexpected elements which the \
-                verifier is unable to parse."
+        tag: Cow::Borrowed("unexpected_evidence"),
+        short: Cow::Borrowed("unexpected evidence"),
+        long:  Cow::Borrowed("The evidence received contains unexpected elements which the \
+                verifier is unable to parse.")
     },
     99i8 => ValueDescription{
-        tag:   "crypto_failed",
-        short: "cryptographic validation failed",
-        long:  "Cryptographic validation of the Evidence has failed.",
+        tag:   Cow::Borrowed("crypto_failed"),
+        short: Cow::Borrowed("cryptographic validation failed"),
+        long:  Cow::Borrowed("Cryptographic validation of the Evidence has failed."),
     },
 };
 
-pub static INSTANCE_CLAIM_DESC: &ClaimDescripiton<'static> = &ClaimDescripiton {
+pub static INSTANCE_CLAIM_DESC: &ClaimDescription<'static> = &ClaimDescription {
     key: 0,
-    name: "instance-identity",
+    name: Cow::Borrowed("instance-identity"),
 };
 
 pub const TRUSTWORTHY_INSTANCE: i8 = 2;
@@ -73,28 +106,28 @@ pub const UNRECOGNIZED_INSTANCE: i8 = 97;
 
 pub static INSTANCE_CLAIM_MAP: &Map<i8, ValueDescription<'static>> = &phf_map! {
     2i8 => ValueDescription{
-        tag:   "recognized_instance",
-        short: "trustworthy instance",
-        long:  "The Attesting Environment is recognized, and the associated \
-                instance of the Attester is not known to be compromised.",
+        tag:   Cow::Borrowed("recognized_instance"),
+        short: Cow::Borrowed("trustworthy instance"),
+        long:  Cow::Borrowed("The Attesting Environment is recognized, and the associated \
+                instance of the Attester is not known to be compromised."),
     },
     96i8 => ValueDescription{
-        tag:   "untrustworthy_instance",
-        short: "recognized but not trustworthy",
-        long:  "The Attesting Environment is recognized, but its unique private key \
-                indicates a device which is not trustworthy.",
+        tag:   Cow::Borrowed("untrustworthy_instance"),
+        short: Cow::Borrowed("recognized but not trustworthy"),
+        long:  Cow::Borrowed("The Attesting Environment is recognized, but its unique private key \
+                indicates a device which is not trustworthy."),
     },
     97i8 => ValueDescription{
-        tag:   "unrecognized_instance",
-        short: "not recognized",
-        long:  "The Attesting Environment is not recognized; however the verifier \
-                believes it should be.",
+        tag:   Cow::Borrowed("unrecognized_instance"),
+        short: Cow::Borrowed("not recognized"),
+        long:  Cow::Borrowed("The Attesting Environment is not recognized; however the verifier \
+                believes it should be."),
     },
 };
 
-pub static CONFIG_CLAIM_DESC: &ClaimDescripiton<'static> = &ClaimDescripiton {
+pub static CONFIG_CLAIM_DESC: &ClaimDescription<'static> = &ClaimDescription {
     key: 1,
-    name: "configuration",
+    name: Cow::Borrowed("configuration"),
 };
 
 pub const APPROVED_CONFIG: i8 = 2;
@@ -105,37 +138,37 @@ pub const UNSUPPORTABLE_CONFIG: i8 = 96;
 
 pub static CONFIG_CLAIM_MAP: &Map<i8, ValueDescription<'static>> = &phf_map! {
     2i8 => ValueDescription{
-        tag:   "approved_config",
-        short: "all recognized and approved",
-        long:  "The configuration is a known and approved config.",
+        tag:   Cow::Borrowed("approved_config"),
+        short: Cow::Borrowed("all recognized and approved"),
+        long:  Cow::Borrowed("The configuration is a known and approved config."),
     },
     3i8 => ValueDescription{
-        tag:   "safe_config",
-        short: "no known vulnerabilities",
-        long:  "The configuration includes or exposes no known vulnerabilities",
+        tag:   Cow::Borrowed("safe_config"),
+        short: Cow::Borrowed("no known vulnerabilities"),
+        long:  Cow::Borrowed("The configuration includes or exposes no known vulnerabilities"),
     },
     32i8 => ValueDescription{
-        tag:   "unsafe_config",
-        short: "known vulnerabilities",
-        long:  "The configuration includes or exposes known vulnerabilities.",
+        tag:   Cow::Borrowed("unsafe_config"),
+        short: Cow::Borrowed("known vulnerabilities"),
+        long:  Cow::Borrowed("The configuration includes or exposes known vulnerabilities."),
     },
     36i8 => ValueDescription{
-        tag:   "unavailable_config",
-        short: "config elements unavailable",
-        long:  "Elements of the configuration relevant to security are unavailable \
-                to the Verifier.",
+        tag:   Cow::Borrowed("unavailable_config"),
+        short: Cow::Borrowed("config elements unavailable"),
+        long:  Cow::Borrowed("Elements of the configuration relevant to security are unavailable \
+                to the Verifier."),
     },
     96i8 => ValueDescription{
-        tag:   "unsupportable_config",
-        short: "unacceptable security vulnerabilities",
-        long:  "The configuration is unsupportable as it exposes unacceptable \
-                security vulnerabilities",
+        tag:   Cow::Borrowed("unsupportable_config"),
+        short: Cow::Borrowed("unacceptable security vulnerabilities"),
+        long:  Cow::Borrowed("The configuration is unsupportable as it exposes unacceptable \
+                security vulnerabilities"),
     },
 };
 
-pub static EXECUTABLES_CLAIM_DESC: &ClaimDescripiton<'static> = &ClaimDescripiton {
+pub static EXECUTABLES_CLAIM_DESC: &ClaimDescription<'static> = &ClaimDescription {
     key: 2,
-    name: "executables",
+    name: Cow::Borrowed("executables"),
 };
 
 pub const APPROVED_RUNTIME: i8 = 2;
@@ -146,41 +179,41 @@ pub const CONTRAINDICATED_RUNTIME: i8 = 96;
 
 pub static EXECUTABLES_CLAIM_MAP: &Map<i8, ValueDescription<'static>> = &phf_map! {
     2i8 => ValueDescription{
-        tag:   "approved_rt",
-        short: "recognized and approved boot- and run-time",
-        long:  "Only a recognized genuine set of approved executables, scripts, files, \
-                and/or objects have been loaded during and after the boot process.",
+        tag:   Cow::Borrowed("approved_rt"),
+        short: Cow::Borrowed("recognized and approved boot- and run-time"),
+        long:  Cow::Borrowed("Only a recognized genuine set of approved executables, scripts, files, \
+                and/or objects have been loaded during and after the boot process."),
     },
     3i8 => ValueDescription{
-            tag:   "approved_boot",
-            short: "recognized and approved boot-time",
-            long:  "Only a recognized genuine set of approved executables have been \
-                    loaded during the boot process.",
+            tag:   Cow::Borrowed("approved_boot"),
+            short: Cow::Borrowed("recognized and approved boot-time"),
+            long:  Cow::Borrowed("Only a recognized genuine set of approved executables have been \
+                    loaded during the boot process."),
     },
     32i8 => ValueDescription{
-        tag:   "unsafe_rt",
-        short: "recognized but known bugs or vulnerabilities",
-        long:  "Only a recognized genuine set of executables, scripts, files, and/or \
+        tag:   Cow::Borrowed("unsafe_rt"),
+        short: Cow::Borrowed("recognized but known bugs or vulnerabilities"),
+        long:  Cow::Borrowed("Only a recognized genuine set of executables, scripts, files, and/or \
                 objects have been loaded. However the Verifier cannot vouch for a subset \
-                of these due to known bugs or other known vulnerabilities.",
+                of these due to known bugs or other known vulnerabilities."),
     },
     33i8 => ValueDescription{
-        tag:   "unrecognized_rt",
-        short: "unrecognized run-time",
-        long:  "Runtime memory includes executables, scripts, files, and/or objects which \
-                are not recognized.",
+        tag:   Cow::Borrowed("unrecognized_rt"),
+        short: Cow::Borrowed("unrecognized run-time"),
+        long:  Cow::Borrowed("Runtime memory includes executables, scripts, files, and/or objects which \
+                are not recognized."),
     },
     96i8 => ValueDescription{
-        tag:   "contraindicated_rt",
-        short: "contraindicated run-time",
-        long:  "Runtime memory includes executables, scripts, files, and/or object which \
-                are contraindicated.",
+        tag:   Cow::Borrowed("contraindicated_rt"),
+        short: Cow::Borrowed("contraindicated run-time"),
+        long:  Cow::Borrowed("Runtime memory includes executables, scripts, files, and/or object which \
+                are contraindicated."),
     },
 };
 
-pub static FILE_SYSTEM_CLAIM_DESC: &ClaimDescripiton<'static> = &ClaimDescripiton {
+pub static FILE_SYSTEM_CLAIM_DESC: &ClaimDescription<'static> = &ClaimDescription {
     key: 3,
-    name: "file-system",
+    name: Cow::Borrowed("file-system"),
 };
 
 pub const APPROVED_FILES: i8 = 2;
@@ -189,25 +222,25 @@ pub const CONTRAINDICATED_FILES: i8 = 96;
 
 pub static FILE_SYSTEM_CLAIM_MAP: &Map<i8, ValueDescription<'static>> = &phf_map! {
     2i8 => ValueDescription{
-        tag:   "approved_fs",
-        short: "all recognized and approved",
-        long:  "Only a recognized set of approved files are found.",
+        tag:   Cow::Borrowed("approved_fs"),
+        short: Cow::Borrowed("all recognized and approved"),
+        long:  Cow::Borrowed("Only a recognized set of approved files are found."),
     },
     32i8 => ValueDescription{
-        tag:   "unrecognized_fs",
-        short: "unrecognized item(s) found",
-        long:  "The file system includes unrecognized executables, scripts, or files.",
+        tag:   Cow::Borrowed("unrecognized_fs"),
+        short: Cow::Borrowed("unrecognized item(s) found"),
+        long:  Cow::Borrowed("The file system includes unrecognized executables, scripts, or files."),
     },
     96i8 => ValueDescription{
-        tag:   "contraindicated_fs",
-        short: "contraindicated item(s) found",
-        long:  "The file system includes contraindicated executables, scripts, or files.",
+        tag:   Cow::Borrowed("contraindicated_fs"),
+        short: Cow::Borrowed("contraindicated item(s) found"),
+        long:  Cow::Borrowed("The file system includes contraindicated executables, scripts, or files."),
     },
 };
 
-pub static HARDWARE_CLAIM_DESC: &ClaimDescripiton<'static> = &ClaimDescripiton {
+pub static HARDWARE_CLAIM_DESC: &ClaimDescription<'static> = &ClaimDescription {
     key: 4,
-    name: "hardware",
+    name: Cow::Borrowed("hardware"),
 };
 
 pub const GENUINE_HARDWARE: i8 = 2;
@@ -217,34 +250,34 @@ pub const UNRECOGNIZED_HARDWARE: i8 = 97;
 
 pub static HARDWARE_CLAIM_MAP: &Map<i8, ValueDescription<'static>> = &phf_map! {
     2i8 => ValueDescription{
-        tag:   "genuine_hw",
-        short: "genuine",
-        long:  "An Attester has passed its hardware and/or firmware verifications \
-                needed to demonstrate that these are genuine/supported.",
+        tag:   Cow::Borrowed("genuine_hw"),
+        short: Cow::Borrowed("genuine"),
+        long:  Cow::Borrowed("An Attester has passed its hardware and/or firmware verifications \
+                needed to demonstrate that these are genuine/supported."),
     },
     32i8 => ValueDescription{
-        tag:   "unsafe_hw",
-        short: "genuine but known bugs or vulnerabilities",
-        long:  "An Attester contains only genuine/supported hardware and/or firmware, \
-                but there are known security vulnerabilities.",
+        tag:   Cow::Borrowed("unsafe_hw"),
+        short: Cow::Borrowed("genuine but known bugs or vulnerabilities"),
+        long:  Cow::Borrowed("An Attester contains only genuine/supported hardware and/or firmware, \
+                but there are known security vulnerabilities."),
     },
     96i8 => ValueDescription{
-        tag:   "contraindicated_hw",
-        short: "genuine but contraindicated",
-        long:  "Attester hardware and/or firmware is recognized, but its trustworthiness \
-                is contraindicated.",
+        tag:   Cow::Borrowed("contraindicated_hw"),
+        short: Cow::Borrowed("genuine but contraindicated"),
+        long:  Cow::Borrowed("Attester hardware and/or firmware is recognized, but its trustworthiness \
+                is contraindicated."),
     },
     97i8 => ValueDescription{
-        tag:   "unrecognized_hw",
-        short: "unrecognized",
-        long:  "A Verifier does not recognize an Attester's hardware or firmware, but it \
-                should be recognized.",
+        tag:   Cow::Borrowed("unrecognized_hw"),
+        short: Cow::Borrowed("unrecognized"),
+        long:  Cow::Borrowed("A Verifier does not recognize an Attester's hardware or firmware, but it \
+                should be recognized."),
     },
 };
 
-pub static RUNTIME_CLAIM_DESC: &ClaimDescripiton<'static> = &ClaimDescripiton {
+pub static RUNTIME_CLAIM_DESC: &ClaimDescription<'static> = &ClaimDescription {
     key: 5,
-    name: "runtime-opaque",
+    name: Cow::Borrowed("runtime-opaque"),
 };
 
 pub const ENCRYPTED_MEMORY_RUNTIME: i8 = 2;
@@ -253,30 +286,30 @@ pub const VISIBLE_MEMORY_RUNTIME: i8 = 96;
 
 pub static RUNTIME_CLAIM_MAP: &Map<i8, ValueDescription<'static>> = &phf_map! {
     2i8 => ValueDescription{
-        tag:   "encrypted_rt",
-        short: "memory encryption",
-        long:  "the Attester's executing Target Environment and Attesting Environments \
+        tag:   Cow::Borrowed("encrypted_rt"),
+        short: Cow::Borrowed("memory encryption"),
+        long:  Cow::Borrowed("the Attester's executing Target Environment and Attesting Environments \
                 are encrypted and within Trusted Execution Environment(s) opaque to \
-                the operating system, virtual machine manager, and peer applications.",
+                the operating system, virtual machine manager, and peer applications."),
     },
     32i8 => ValueDescription{
-        tag:   "isolated_rt",
-        short: "memory isolation",
-        long:  "the Attester's executing Target Environment and Attesting Environments \
+        tag:   Cow::Borrowed("isolated_rt"),
+        short: Cow::Borrowed("memory isolation"),
+        long:  Cow::Borrowed("the Attester's executing Target Environment and Attesting Environments \
                 are inaccessible from any other parallel application or Guest VM running \
-                on the Attester's physical device.",
+                on the Attester's physical device."),
     },
     96i8 => ValueDescription{
-        tag:   "visible_rt",
-        short: "visible",
-        long:  "The Verifier has concluded that in memory objects are unacceptably visible \
-                within the physical host that supports the Attester.",
+        tag:   Cow::Borrowed("visible_rt"),
+        short: Cow::Borrowed("visible"),
+        long:  Cow::Borrowed("The Verifier has concluded that in memory objects are unacceptably visible \
+                within the physical host that supports the Attester."),
     },
 };
 
-pub static STORAGE_CLAIM_DESC: &ClaimDescripiton<'static> = &ClaimDescripiton {
+pub static STORAGE_CLAIM_DESC: &ClaimDescription<'static> = &ClaimDescription {
     key: 6,
-    name: "storage-opaque",
+    name: Cow::Borrowed("storage-opaque"),
 };
 
 pub const HW_KEYS_ENCRYPTED_SECRETS: i8 = 2;
@@ -285,28 +318,28 @@ pub const UNENCRYPTED_SECRETS: i8 = 96;
 
 pub static STORAGE_CLAIM_MAP: &Map<i8, ValueDescription<'static>> = &phf_map! {
     2i8 => ValueDescription{
-        tag:   "hw_encrypted_secrets",
-        short: "encrypted secrets with HW-backed keys",
-        long:  "the Attester encrypts all secrets in persistent storage via using keys \
+        tag:   Cow::Borrowed("hw_encrypted_secrets"),
+        short: Cow::Borrowed("encrypted secrets with HW-backed keys"),
+        long:  Cow::Borrowed("the Attester encrypts all secrets in persistent storage via using keys \
                 which are never visible outside an HSM or the Trusted Execution Environment \
-                hardware.",
+                hardware."),
     },
     32i8 => ValueDescription{
-        tag:   "sw_encrypted_secrets",
-        short: "encrypted secrets with non HW-backed keys",
-        long:  "the Attester encrypts all persistently stored secrets, but without using \
-                hardware backed keys.",
+        tag:   Cow::Borrowed("sw_encrypted_secrets"),
+        short: Cow::Borrowed("encrypted secrets with non HW-backed keys"),
+        long:  Cow::Borrowed("the Attester encrypts all persistently stored secrets, but without using \
+                hardware backed keys."),
     },
     96i8 => ValueDescription{
-        tag:   "unencrypted_secrets",
-        short: "unencrypted secrets",
-        long:  "There are persistent secrets which are stored unencrypted in an Attester.",
+        tag:   Cow::Borrowed("unencrypted_secrets"),
+        short: Cow::Borrowed("unencrypted secrets"),
+        long:  Cow::Borrowed("There are persistent secrets which are stored unencrypted in an Attester."),
     },
 };
 
-pub static SOURCED_DATA_CLAIM_DESC: &ClaimDescripiton<'static> = &ClaimDescripiton {
+pub static SOURCED_DATA_CLAIM_DESC: &ClaimDescription<'static> = &ClaimDescription {
     key: 7,
-    name: "sourced-data",
+    name: Cow::Borrowed("sourced-data"),
 };
 
 pub const TRUSTED_SOURCES: i8 = 2;
@@ -315,23 +348,23 @@ pub const CONTRAINDICATED_SOURCES: i8 = 96;
 
 pub static SOURCED_DATA_CLAIM_MAP: &Map<i8, ValueDescription<'static>> = &phf_map! {
     2i8 => ValueDescription{
-        tag:   "trusted_sources",
-        short: "from attesters in the affirming tier",
-        long:  "All essential Attester source data objects have been provided by other \
+        tag:   Cow::Borrowed("trusted_sources"),
+        short: Cow::Borrowed("from attesters in the affirming tier"),
+        long:  Cow::Borrowed("All essential Attester source data objects have been provided by other \
                 Attester(s) whose most recent appraisal(s) had both no Trustworthiness \
                 Claims of \"0\" where the current Trustworthiness Claim is \"Affirmed\", \
-                as well as no \"Warning\" or \"Contraindicated\" Trustworthiness Claims.",
+                as well as no \"Warning\" or \"Contraindicated\" Trustworthiness Claims."),
     },
     32i8 => ValueDescription{
-        tag:   "untrusted_sources",
-        short: "from unattested sources or attesters in the warning tier",
-        long:  "Attester source data objects come from unattested sources, or attested \
-                sources with \"Warning\" type Trustworthiness Claims",
+        tag:   Cow::Borrowed("untrusted_sources"),
+        short: Cow::Borrowed("from unattested sources or attesters in the warning tier"),
+        long:  Cow::Borrowed("Attester source data objects come from unattested sources, or attested \
+                sources with \"Warning\" type Trustworthiness Claims"),
     },
     96i8 => ValueDescription{
-        tag:   "contraindicated_sources",
-        short: "from attesters in the contraindicated tier",
-        long:  "Attester source data objects come from contraindicated sources.",
+        tag:   Cow::Borrowed("contraindicated_sources"),
+        short: Cow::Borrowed("from attesters in the contraindicated tier"),
+        long:  Cow::Borrowed("Attester source data objects come from contraindicated sources."),
     },
 };
 
@@ -340,24 +373,66 @@ pub static SOURCED_DATA_CLAIM_MAP: &Map<i8, ValueDescription<'static>> = &phf_ma
 /// This is a claim regarding the trustworthiness of one aspect of the attested environment, as
 /// defined in
 /// <https://datatracker.ietf.org/doc/html/draft-ietf-rats-ar4si-04#name-trustworthiness-claims>
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct TrustClaim {
     /// Claim value
     pub value: Option<i8>,
-    desc: &'static ClaimDescripiton<'static>,
-    value_desc: &'static Map<i8, ValueDescription<'static>>,
+    desc: Cow<'static, ClaimDescription<'static>>,
+    value_desc: ValueDescriptions,
+}
+
+// The descriptions of a claim's values: either one of the static maps of the standard claims, or
+// owned by a claim defined at runtime (shared, so that cloning the claim does not copy them).
+#[derive(Debug, Clone)]
+enum ValueDescriptions {
+    Static(&'static Map<i8, ValueDescription<'static>>),
+    Owned(Arc<BTreeMap<i8, ValueDescription<'static>>>),
+}
+
+impl ValueDescriptions {
+    fn get(&self, val: &i8) -> Option<&ValueDescription<'static>> {
+        match self {
+            ValueDescriptions::Static(m) => m.get(val),
+            ValueDescriptions::Owned(m) => m.get(val),
+        }
+    }
+
+    fn find_tag(&self, tag: &str) -> Option<i8> {
+        match self {
+            ValueDescriptions::Static(m) => {
+                m.entries().find(|(_, v)| v.tag == tag).map(|(k, _)| *k)
+            }
+            ValueDescriptions::Owned(m) => m.iter().find(|(_, v)| v.tag == tag).map(|(k, _)| *k),
+        }
+    }
 }
 
 impl TrustClaim {
     /// Create a new claim based on the specified descriptions
     pub fn new(
-        desc_map: &'static ClaimDescripiton<'static>,
+        desc_map: &'static ClaimDescription<'static>,
         val_desc_map: &'static Map<i8, ValueDescription<'static>>,
     ) -> TrustClaim {
         TrustClaim {
             value: None,
-            desc: desc_map,
-            value_desc: val_desc_map,
+            desc: Cow::Borrowed(desc_map),
+            value_desc: ValueDescriptions::Static(val_desc_map),
+        }
+    }
+
+    /// Create a new claim based on descriptions constructed at runtime
+    ///
+    /// Unlike [`TrustClaim::new`], this does not require the descriptions to be static, allowing
+    /// claims to be defined dynamically (e.g. from configuration). Borrowed descriptions may be
+    /// converted using [`ClaimDescription::into_static`] and [`ValueDescription::into_static`].
+    pub fn new_owned(
+        desc: ClaimDescription<'static>,
+        val_descs: BTreeMap<i8, ValueDescription<'static>>,
+    ) -> TrustClaim {
+        TrustClaim {
+            value: None,
+            desc: Cow::Owned(desc),
+            value_desc: ValueDescriptions::Owned(Arc::new(val_descs)),
         }
     }
 
@@ -371,6 +446,36 @@ impl TrustClaim {
         self.value = Some(v);
     }
 
+    /// Set the claim to the value with the specified string tag
+    ///
+    /// The tag may be one of those common to all claims (e.g. `"no_claim"`), or one specific to
+    /// this claim (e.g. `"approved_config"` for the configuration claim). A tag prefixed with
+    /// `"-"` sets the negative (mirrored) value (see [`TrustClaim::is_mirrored`]).
+    pub fn set_by_tag(&mut self, tag: &str) -> Result<(), Error> {
+        let (name, mirrored) = match tag.strip_prefix('-') {
+            Some(t) => (t, true),
+            None => (tag, false),
+        };
+
+        let found = COMMON_CLAIM_MAP
+            .entries()
+            .find(|(_, v)| v.tag == name)
+            .map(|(k, _)| *k)
+            .or_else(|| self.value_desc.find_tag(name));
+
+        match found {
+            Some(k) if !mirrored => {
+                self.set(k);
+                Ok(())
+            }
+            Some(k) if k > 1 => {
+                self.set(-k);
+                Ok(())
+            }
+            _ => Err(Error::InvalidName(tag.to_string())),
+        }
+    }
+
     /// Return the claim's value
     ///
     /// If the value is unset, `0i8` is returned, indicating that no claim is being made.
@@ -392,7 +497,7 @@ impl TrustClaim {
 
     /// Get the string tag of the claim
     pub fn tag(&self) -> &str {
-        self.desc.name
+        &self.desc.name
     }
 
     /// Get the integer key of the claim
@@ -403,12 +508,14 @@ impl TrustClaim {
     /// Get the string name of the claim's value
     ///
     /// If the value is one of those defined by [draft-ietf-rats-ar4si-04], its standard name is
-    /// returned. Otherwise, the name is `"TrustClaim(i)"`, where `i` is the value.
+    /// returned (see [`TrustClaim::is_mirrored`] for negative values). Otherwise, the name is
+    /// `"TrustClaim(i)"`, where `i` is the value.
     ///
     /// [draft-ietf-rats-ar4si-04]: https://datatracker.ietf.org/doc/html/draft-ietf-rats-ar4si-04
     pub fn value_name(&self) -> String {
         match self.value_desc() {
-            Some(v) => v.tag.to_string(),
+            Some((v, false)) => v.tag.to_string(),
+            Some((v, true)) => format!("-{}", v.tag),
             None => format!("TrustClaim({})", self.value()),
         }
     }
@@ -421,7 +528,8 @@ impl TrustClaim {
     /// [draft-ietf-rats-ar4si-04]: https://datatracker.ietf.org/doc/html/draft-ietf-rats-ar4si-04
     pub fn value_short_desc(&self) -> String {
         match self.value_desc() {
-            Some(v) => v.short.to_string(),
+            Some((v, false)) => v.short.to_string(),
+            Some((v, true)) => format!("{}{MIRRORED_DESC_SUFFIX}", v.short),
             None => "".to_string(),
         }
     }
@@ -434,7 +542,8 @@ impl TrustClaim {
     /// [draft-ietf-rats-ar4si-04]: https://datatracker.ietf.org/doc/html/draft-ietf-rats-ar4si-04
     pub fn value_long_desc(&self) -> String {
         match self.value_desc() {
-            Some(v) => v.long.to_string(),
+            Some((v, false)) => v.long.to_string(),
+            Some((v, true)) => format!("{}{MIRRORED_DESC_SUFFIX}", v.long),
             None => "".to_string(),
         }
     }
@@ -455,8 +564,36 @@ impl TrustClaim {
         }
     }
 
-    fn value_desc(&self) -> Option<&ValueDescription> {
+    /// Return `true` if the claim's value is a negative "mirror" of a defined positive value
+    ///
+    /// Negative values (other than `-1`, which has its own meaning) that are not explicitly defined
+    /// are treated as the corresponding positive value, but sourced from attestation rather than
+    /// verification. Such values take their name and descriptions from the positive value, marked
+    /// to indicate their negative origin: the name is prefixed with `"-"` (e.g.
+    /// `"-approved_config"` for `-2` in the configuration claim), and the descriptions are suffixed
+    /// with `" (sourced from attestation)"`.
+    pub fn is_mirrored(&self) -> bool {
+        matches!(self.value_desc(), Some((_, true)))
+    }
+
+    // Return the description of the claim's value, along with whether it is mirrored from that of
+    // the corresponding positive value.
+    fn value_desc(&self) -> Option<(&ValueDescription<'static>, bool)> {
         let val = self.value();
+        if let Some(desc) = self.lookup_value_desc(val) {
+            return Some((desc, false));
+        }
+
+        if val < -1 {
+            return self
+                .lookup_value_desc(val.checked_neg()?)
+                .map(|desc| (desc, true));
+        }
+
+        None
+    }
+
+    fn lookup_value_desc(&self, val: i8) -> Option<&ValueDescription<'static>> {
         if (-1..=1).contains(&val) || val == 99 {
             return COMMON_CLAIM_MAP.get(&val);
         }
@@ -554,11 +691,8 @@ mod test {
 
     #[test]
     fn equality() {
-        let claim: TrustClaim = TrustClaim {
-            value: Some(2i8),
-            desc: INSTANCE_CLAIM_DESC,
-            value_desc: INSTANCE_CLAIM_MAP,
-        };
+        let mut claim = TrustClaim::new(INSTANCE_CLAIM_DESC, INSTANCE_CLAIM_MAP);
+        claim.set(2i8);
         assert_eq!(claim, claim.clone());
         assert_eq!(claim, TRUSTWORTHY_INSTANCE);
         assert_eq!(claim, 2i8);
@@ -567,11 +701,7 @@ mod test {
 
     #[test]
     fn tier() {
-        let mut claim: TrustClaim = TrustClaim {
-            value: None,
-            desc: INSTANCE_CLAIM_DESC,
-            value_desc: INSTANCE_CLAIM_MAP,
-        };
+        let mut claim = TrustClaim::new(INSTANCE_CLAIM_DESC, INSTANCE_CLAIM_MAP);
 
         assert_eq!(claim.tier(), TrustTier::None);
 
@@ -611,4 +741,97 @@ mod test {
         claim.set(-97);
         assert_eq!(claim.tier(), TrustTier::Contraindicated);
     }
+
+    #[test]
+    fn mirrored() {
+        let mut claim = TrustClaim::try_from("configuration").unwrap();
+
+        claim.set(-2i8);
+        assert!(claim.is_mirrored());
+        assert_eq!(claim.value_name(), "-approved_config");
+        assert_eq!(
+            claim.value_short_desc(),
+            "all recognized and approved (sourced from attestation)"
+        );
+        assert_eq!(
+            claim.value_long_desc(),
+            "The configuration is a known and approved config. (sourced from attestation)"
+        );
+        assert_eq!(claim.tier(), TrustTier::Affirming);
+
+        // -1 has its own meaning, and so is not a mirror of 1
+        claim.set(-1i8);
+        assert!(!claim.is_mirrored());
+        assert_eq!(claim.value_name(), "verifier_malfunction");
+
+        claim.set(-4i8);
+        assert!(!claim.is_mirrored());
+        assert_eq!(claim.value_name(), "TrustClaim(-4)");
+
+        claim.set(i8::MIN);
+        assert_eq!(claim.value_name(), "TrustClaim(-128)");
+
+        claim.set_by_tag("-unsafe_config").unwrap();
+        assert_eq!(claim, -UNSAFE_CONFIG);
+        assert_eq!(claim, "-unsafe_config");
+
+        assert!(
+            matches!(claim.set_by_tag("-no_claim"), Err(Error::InvalidName(t))
+                if t == "-no_claim")
+        );
+    }
+
+    #[test]
+    fn set_by_tag() {
+        let mut claim = TrustClaim::try_from("configuration").unwrap();
+
+        claim.set_by_tag("approved_config").unwrap();
+        assert_eq!(claim, APPROVED_CONFIG);
+
+        claim.set_by_tag("crypto_failed").unwrap();
+        assert_eq!(claim, CRYPTO_VALIDATION_FAILED);
+
+        assert!(
+            matches!(claim.set_by_tag("approved_rt"), Err(Error::InvalidName(t))
+                if t == "approved_rt")
+        );
+        assert_eq!(claim, CRYPTO_VALIDATION_FAILED);
+    }
+
+    #[test]
+    fn owned_descriptions() {
+        let name = String::from("custom-claim");
+        let long = format!("The {name} value was computed at runtime.");
+
+        let desc = ClaimDescription {
+            key: 42,
+            name: Cow::Borrowed(name.as_str()),
+        }
+        .into_static();
+        let val_desc = ValueDescription {
+            tag: Cow::Borrowed("custom_value"),
+            short: Cow::Borrowed("custom value"),
+            long: Cow::Borrowed(long.as_str()),
+        }
+        .into_static();
+        drop(name);
+
+        let mut claim = TrustClaim::new_owned(desc, BTreeMap::from([(2i8, val_desc)]));
+        assert_eq!(claim.tag(), "custom-claim");
+        assert_eq!(claim.key(), 42);
+
+        claim.set_by_tag("custom_value").unwrap();
+        assert_eq!(claim.value(), 2);
+        assert_eq!(claim.value_name(), "custom_value");
+        assert_eq!(
+            claim.value_long_desc(),
+            "The custom-claim value was computed at runtime."
+        );
+
+        let claim2 = claim.clone();
+        assert_eq!(claim2.value_short_desc(), "custom value");
+
+        claim.set(0);
+        assert_eq!(claim.value_name(), "no_claim");
+    }
 }
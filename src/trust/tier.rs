@@ -11,7 +11,13 @@ use std::fmt;
 ///
 /// This is a categorisation of the levels of trustworthiness based on the values assigned to
 /// trustworthiness claims.
-#[derive(Debug, PartialEq, PartialOrd)]
+///
+/// Only the representative values of the tiers (see [`TrustTier::value`]) are accepted when
+/// deserializing: any other value (including negative values, which are otherwise permitted for
+/// claims) is rejected with an error listing the valid tiers, rather than being mapped to the
+/// nearest tier. This is because a status outside of these is not meaningful to a relying party,
+/// and guessing at its intent could over-state the trustworthiness of the attester.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub enum TrustTier {
     None,
     Affirming,
@@ -19,6 +25,42 @@ pub enum TrustTier {
     Contraindicated,
 }
 
+impl TrustTier {
+    /// Return the representative claim value for the tier
+    ///
+    /// This is the value used to represent the tier as a whole (e.g. when serializing it to CBOR),
+    /// rather than any of the claim-specific values within the tier.
+    pub fn value(&self) -> i8 {
+        match self {
+            TrustTier::None => 0,
+            TrustTier::Affirming => 2,
+            TrustTier::Warning => 32,
+            TrustTier::Contraindicated => 96,
+        }
+    }
+}
+
+/// The "traffic light" color summarising a trustworthiness claim
+///
+/// Some systems only report the color of each claim, rather than its exact value. Each color
+/// corresponds to a [`TrustTier`] (other than [`TrustTier::None`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    Green,
+    Amber,
+    Red,
+}
+
+impl From<Color> for TrustTier {
+    fn from(color: Color) -> TrustTier {
+        match color {
+            Color::Green => TrustTier::Affirming,
+            Color::Amber => TrustTier::Warning,
+            Color::Red => TrustTier::Contraindicated,
+        }
+    }
+}
+
 impl Serialize for TrustTier {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -32,12 +74,7 @@ impl Serialize for TrustTier {
                 TrustTier::Contraindicated => serializer.serialize_str("contraindicated"),
             }
         } else {
-            match self {
-                TrustTier::None => serializer.serialize_i8(0),
-                TrustTier::Affirming => serializer.serialize_i8(2),
-                TrustTier::Warning => serializer.serialize_i8(32),
-                TrustTier::Contraindicated => serializer.serialize_i8(96),
-            }
+            serializer.serialize_i8(self.value())
         }
     }
 }
@@ -47,11 +84,39 @@ impl<'de> Deserialize<'de> for TrustTier {
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_any(TrustTierVisitor)
+        deserializer.deserialize_any(TrustTierVisitor { lenient: false })
+    }
+}
+
+// A TrustTier that may also be decoded from a boolean, as some legacy producers encode a simple
+// pass/fail in place of a tier. This is a compatibility shim, only used for lenient decoding (see
+// Ear::from_json_lenient): true is decoded as affirming, and false as contraindicated.
+pub(crate) struct LenientTrustTier(pub TrustTier);
+
+impl<'de> Deserialize<'de> for LenientTrustTier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(TrustTierVisitor { lenient: true })
+            .map(LenientTrustTier)
     }
 }
 
-struct TrustTierVisitor;
+// lists the values and names of the valid tiers, for use in error messages
+const VALID_TIERS: &str = "0 (none), 2 (affirming), 32 (warning), 96 (contraindicated)";
+
+fn unexpected_tier<E: de::Error>(value: impl fmt::Display) -> E {
+    E::custom(format!(
+        "Unexpected TrustTier value: {value} (valid tiers are {VALID_TIERS})"
+    ))
+}
+
+struct TrustTierVisitor {
+    // accept booleans (see LenientTrustTier)
+    lenient: bool,
+}
 
 impl Visitor<'_> for TrustTierVisitor {
     type Value = TrustTier;
@@ -60,6 +125,17 @@ impl Visitor<'_> for TrustTierVisitor {
         formatter.write_str("a string or an integer between -128 and 127")
     }
 
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match (self.lenient, value) {
+            (true, true) => Ok(TrustTier::Affirming),
+            (true, false) => Ok(TrustTier::Contraindicated),
+            (false, _) => Err(E::invalid_type(de::Unexpected::Bool(value), &self)),
+        }
+    }
+
     fn visit_i8<E>(self, value: i8) -> Result<Self::Value, E>
     where
         E: de::Error,
@@ -69,7 +145,7 @@ impl Visitor<'_> for TrustTierVisitor {
             2 => Ok(TrustTier::Affirming),
             32 => Ok(TrustTier::Warning),
             96 => Ok(TrustTier::Contraindicated),
-            _ => Err(E::custom(format!("Unexpected TrustTier value: {value}"))),
+            _ => Err(unexpected_tier(value)),
         }
     }
 
@@ -78,7 +154,7 @@ impl Visitor<'_> for TrustTierVisitor {
         E: de::Error,
     {
         if value < i16::from(i8::MIN) || value > i16::from(i8::MAX) {
-            return Err(E::custom(format!("Unexpected TrustTier value: {value}")));
+            return Err(unexpected_tier(value));
         }
         self.visit_i8(value as i8)
     }
@@ -88,7 +164,7 @@ impl Visitor<'_> for TrustTierVisitor {
         E: de::Error,
     {
         if value < i32::from(i8::MIN) || value > i32::from(i8::MAX) {
-            return Err(E::custom(format!("Unexpected TrustTier value: {value}")));
+            return Err(unexpected_tier(value));
         }
         self.visit_i8(value as i8)
     }
@@ -98,7 +174,7 @@ impl Visitor<'_> for TrustTierVisitor {
         E: de::Error,
     {
         if value < i64::from(i8::MIN) || value > i64::from(i8::MAX) {
-            return Err(E::custom(format!("Unexpected TrustTier value: {value}")));
+            return Err(unexpected_tier(value));
         }
         self.visit_i8(value as i8)
     }
@@ -108,7 +184,7 @@ impl Visitor<'_> for TrustTierVisitor {
         E: de::Error,
     {
         if value > u64::try_from(i8::MAX).ok().unwrap() {
-            return Err(E::custom(format!("Unexpected TrustTier value: {value}")));
+            return Err(unexpected_tier(value));
         }
         self.visit_i8(value as i8)
     }
@@ -122,7 +198,7 @@ impl Visitor<'_> for TrustTierVisitor {
             "affirming" => Ok(TrustTier::Affirming),
             "warning" => Ok(TrustTier::Warning),
             "contraindicated" => Ok(TrustTier::Contraindicated),
-            _ => Err(E::custom(format!("Unexpected TrustTier value: {value}"))),
+            _ => Err(unexpected_tier(value)),
         }
     }
 }
@@ -155,7 +231,41 @@ mod test {
         let res: Result<TrustTier, CborError<std::io::Error>> = from_reader(buf2.as_slice());
         assert_eq!(
             res.unwrap_err().to_string().as_str(),
-            "Semantic(None, \"Unexpected TrustTier value: -2\")"
+            "Semantic(None, \"Unexpected TrustTier value: -2 (valid tiers are 0 (none), \
+             2 (affirming), 32 (warning), 96 (contraindicated))\")"
         );
     }
+
+    #[test]
+    fn unexpected_value() {
+        // a tier not in the set of representative values is rejected, rather than being mapped to
+        // the nearest tier
+        for buf in [vec![0x38, 0x7f], vec![0x18, 0x40], vec![0x19, 0x01, 0x00]] {
+            let res: Result<TrustTier, CborError<std::io::Error>> = from_reader(buf.as_slice());
+            let msg = res.unwrap_err().to_string();
+            assert!(
+                msg.ends_with(
+                    "(valid tiers are 0 (none), 2 (affirming), 32 (warning), \
+                               96 (contraindicated))\")"
+                ),
+                "{msg}"
+            );
+        }
+
+        let res: Result<TrustTier, _> = serde_json::from_str("\"failing\"");
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Unexpected TrustTier value: failing (valid tiers are 0 (none), 2 (affirming), \
+             32 (warning), 96 (contraindicated)) at line 1 column 9"
+        );
+    }
+
+    #[test]
+    fn color() {
+        assert_eq!(TrustTier::from(Color::Green), TrustTier::Affirming);
+        assert_eq!(TrustTier::from(Color::Amber), TrustTier::Warning);
+        assert_eq!(TrustTier::from(Color::Red), TrustTier::Contraindicated);
+
+        assert_eq!(TrustTier::from(Color::Amber).value(), 32);
+    }
 }
@@ -8,10 +8,13 @@ use serde::{
 };
 
 use super::claim::*;
+use super::tier::{Color, TrustTier};
 use crate::error::Error;
+use crate::num::Int;
+use crate::raw::MapKey;
 
 /// The set of trustworthiness claims that may be inserted into an attest result by a verifier
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TrustVector {
     pub instance_identity: TrustClaim,
     pub configuration: TrustClaim,
@@ -38,6 +41,71 @@ impl TrustVector {
         }
     }
 
+    /// Create a new trustworthiness vector, setting the claims with the specified keys to the
+    /// corresponding values (claims not in `pairs` are unset)
+    pub fn from_pairs(pairs: &[(i32, i8)]) -> Result<TrustVector, Error> {
+        let mut tv = TrustVector::new();
+
+        for (key, value) in pairs {
+            tv.mut_by_key(*key)?.set(*value);
+        }
+
+        Ok(tv)
+    }
+
+    /// Create a new trustworthiness vector, setting the claims with the specified names (as
+    /// accepted by [`TrustVector::mut_by_name`]) to the corresponding values (claims not in
+    /// `pairs` are unset)
+    pub fn from_named_pairs(pairs: &[(&str, i8)]) -> Result<TrustVector, Error> {
+        let mut tv = TrustVector::new();
+
+        for (name, value) in pairs {
+            tv.mut_by_name(name)?.set(*value);
+        }
+
+        Ok(tv)
+    }
+
+    /// Create a new trustworthiness vector from the claim values in key order (see
+    /// [`TrustVector::to_array`])
+    pub fn from_array(values: [Option<i8>; 8]) -> TrustVector {
+        let mut tv = TrustVector::new();
+
+        for (claim, value) in [
+            &mut tv.instance_identity,
+            &mut tv.configuration,
+            &mut tv.executables,
+            &mut tv.file_system,
+            &mut tv.hardware,
+            &mut tv.runtime_opaque,
+            &mut tv.storage_opaque,
+            &mut tv.sourced_data,
+        ]
+        .into_iter()
+        .zip(values)
+        {
+            claim.value = value;
+        }
+
+        tv
+    }
+
+    /// Return the values of the claims in the vector, in key order (unset claims are `None`)
+    ///
+    /// This is a compact numeric view of the vector, e.g. for comparing vectors element-wise.
+    pub fn to_array(&self) -> [Option<i8>; 8] {
+        [
+            self.instance_identity.value,
+            self.configuration.value,
+            self.executables.value,
+            self.file_system.value,
+            self.hardware.value,
+            self.runtime_opaque.value,
+            self.storage_opaque.value,
+            self.sourced_data.value,
+        ]
+    }
+
     /// Set all claims in the vector to the specified value
     ///
     /// This only meaningful for values that have common meaning across all claims, such as `0i8`.
@@ -52,9 +120,127 @@ impl TrustVector {
         self.sourced_data.set(v);
     }
 
+    /// Set all claims in the vector to the representative value of the specified tier (see
+    /// [`TrustTier::value`])
+    ///
+    /// Unlike most claim values, these have the same meaning for all claims, so this may be used to
+    /// quickly produce a uniformly affirming (or contraindicated, etc.) vector.
+    pub fn set_all_to_tier(&mut self, tier: TrustTier) {
+        self.set_all(tier.value());
+    }
+
+    /// Unset all claims in the vector
+    pub fn clear(&mut self) {
+        self.instance_identity.unset();
+        self.configuration.unset();
+        self.executables.unset();
+        self.file_system.unset();
+        self.hardware.unset();
+        self.runtime_opaque.unset();
+        self.storage_opaque.unset();
+        self.sourced_data.unset();
+    }
+
+    /// Unset the claim associated with the specified name in this vector
+    pub fn unset_by_name(&mut self, name: &str) -> Result<(), Error> {
+        self.mut_by_name(name)?.unset();
+        Ok(())
+    }
+
+    /// Unset the claim associated with the specified key in this vector
+    pub fn unset_by_key(&mut self, key: i32) -> Result<(), Error> {
+        self.mut_by_key(key)?.unset();
+        Ok(())
+    }
+
+    /// Set claims in the vector from a policy expression
+    ///
+    /// The expression is a comma-separated list of `name=tag` pairs, e.g.
+    /// `"configuration=approved_config, executables=unrecognized_rt"`, where `name` is a claim
+    /// name (as accepted by [`TrustVector::mut_by_name`]) and `tag` is the string tag of one of
+    /// the claim's values. Errors for all invalid pairs are collected and reported together, each
+    /// prefixed with the byte offset of the pair within the expression. If any pair is invalid,
+    /// the vector is left unchanged.
+    pub fn set_from_expr(&mut self, expr: &str) -> Result<(), Error> {
+        let mut tv = self.clone();
+        let mut errors: Vec<String> = Vec::new();
+        let mut offset = 0usize;
+
+        for item in expr.split(',') {
+            let pos = offset + (item.len() - item.trim_start().len());
+            offset += item.len() + 1;
+
+            let item = item.trim();
+            if item.is_empty() && expr.trim().is_empty() {
+                continue;
+            }
+
+            let (name, tag) = match item.split_once('=') {
+                Some((n, t)) => (n.trim(), t.trim()),
+                None => {
+                    errors.push(format!("at {pos}: expected name=tag, but found \"{item}\""));
+                    continue;
+                }
+            };
+
+            let claim = match tv.mut_by_name(name) {
+                Ok(c) => c,
+                Err(_) => {
+                    errors.push(format!("at {pos}: unknown claim name \"{name}\""));
+                    continue;
+                }
+            };
+
+            if claim.set_by_tag(tag).is_err() {
+                errors.push(format!(
+                    "at {pos}: unknown value tag \"{tag}\" for claim \"{}\"",
+                    claim.tag()
+                ));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(Error::ParseError(errors.join("; ")));
+        }
+
+        *self = tv;
+        Ok(())
+    }
+
+    /// Set claims in the vector from their colors
+    ///
+    /// Each named claim (as accepted by [`TrustVector::mut_by_name`]) is set to the
+    /// representative value of the tier corresponding to its color (see [`TrustTier::value`]).
+    /// If any of the names is invalid, the vector is left unchanged.
+    pub fn set_from_colors(&mut self, colors: &[(&str, Color)]) -> Result<(), Error> {
+        let mut tv = self.clone();
+
+        for (name, color) in colors {
+            tv.mut_by_name(name)?.set(TrustTier::from(*color).value());
+        }
+
+        *self = tv;
+        Ok(())
+    }
+
+    /// Return an iterator over references to the claims in the vector, in key order
+    pub fn iter(&self) -> std::array::IntoIter<&TrustClaim, 8> {
+        [
+            &self.instance_identity,
+            &self.configuration,
+            &self.executables,
+            &self.file_system,
+            &self.hardware,
+            &self.runtime_opaque,
+            &self.storage_opaque,
+            &self.sourced_data,
+        ]
+        .into_iter()
+    }
+
     /// Return `true` if any of the claims in the vector have been set, and `false` otherwise
     pub fn any_set(&self) -> bool {
-        for claim in self.into_iter() {
+        for claim in self {
             if claim.is_set() {
                 return true;
             }
@@ -64,8 +250,11 @@ impl TrustVector {
     }
 
     /// Return a reference to a `TrustClaim` associated with the specified name in this vector
+    ///
+    /// Underscores in the name are treated as hyphens (e.g. `"file_system"` is equivalent to
+    /// `"file-system"`).
     pub fn by_name(&self, name: &str) -> Result<&TrustClaim, Error> {
-        match name {
+        match normalize_name(name).as_str() {
             "instance-identity" => Ok(&self.instance_identity),
             "configuration" => Ok(&self.configuration),
             "executables" => Ok(&self.executables),
@@ -80,8 +269,11 @@ impl TrustVector {
 
     /// Return a mutable reference to a `TrustClaim` associated with the specified name in this
     /// vector
+    ///
+    /// Underscores in the name are treated as hyphens (e.g. `"file_system"` is equivalent to
+    /// `"file-system"`).
     pub fn mut_by_name(&mut self, name: &str) -> Result<&mut TrustClaim, Error> {
-        match name {
+        match normalize_name(name).as_str() {
             "instance-identity" => Ok(&mut self.instance_identity),
             "configuration" => Ok(&mut self.configuration),
             "executables" => Ok(&mut self.executables),
@@ -126,6 +318,12 @@ impl TrustVector {
     }
 }
 
+// Producers disagree on whether claim names are hyphenated or use underscores, so both are
+// accepted (the canonical, serialized, form is hyphenated).
+fn normalize_name(name: &str) -> String {
+    name.replace('_', "-")
+}
+
 impl Default for TrustVector {
     fn default() -> Self {
         Self::new()
@@ -141,6 +339,15 @@ impl IntoIterator for TrustVector {
     }
 }
 
+impl<'a> IntoIterator for &'a TrustVector {
+    type Item = &'a TrustClaim;
+    type IntoIter = std::array::IntoIter<&'a TrustClaim, 8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 pub struct TrustVectorIterator {
     tv: TrustVector,
     index: i32,
@@ -155,7 +362,7 @@ impl Iterator for TrustVectorIterator {
             Err(_) => return None,
         };
         self.index += 1;
-        Some(*result)
+        Some(result.clone())
     }
 }
 
@@ -166,7 +373,7 @@ impl Serialize for TrustVector {
     {
         let is_human_readable = serializer.is_human_readable();
         let mut map = serializer.serialize_map(None)?;
-        for claim in self.into_iter() {
+        for claim in self {
             if claim.is_set() {
                 if is_human_readable {
                     map.serialize_entry(claim.tag(), &claim.value())?;
@@ -188,12 +395,38 @@ impl<'de> Deserialize<'de> for TrustVector {
 
         deserializer.deserialize_map(TrustVectorVisitor {
             is_human_readable: is_hr,
+            lenient: false,
         })
     }
 }
 
+// A TrustVector whose claims may be identified by either their names or their keys, regardless
+// of the serialization format, and with both forms mixed within the same map. Some producers
+// (and some chains of producers) emit such vectors, though they are malformed. Numeric strings
+// (as JSON map keys are always strings) are treated as keys. This is only used for lenient
+// decoding (see Ear::from_json_lenient).
+pub(crate) struct LenientTrustVector(pub TrustVector);
+
+impl<'de> Deserialize<'de> for LenientTrustVector {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let is_hr = deserializer.is_human_readable();
+
+        deserializer
+            .deserialize_map(TrustVectorVisitor {
+                is_human_readable: is_hr,
+                lenient: true,
+            })
+            .map(LenientTrustVector)
+    }
+}
+
 struct TrustVectorVisitor {
     pub is_human_readable: bool,
+    // accept claims identified by either name or key (see LenientTrustVector)
+    pub lenient: bool,
 }
 
 impl<'de> Visitor<'de> for TrustVectorVisitor {
@@ -210,19 +443,27 @@ impl<'de> Visitor<'de> for TrustVectorVisitor {
         let mut tv = TrustVector::new();
 
         loop {
-            if self.is_human_readable {
-                match access.next_entry::<&str, i8>()? {
+            if self.lenient {
+                match access.next_key::<MapKey>()? {
+                    Some(k) => {
+                        let claim = mut_by_map_key(&mut tv, &k).map_err(de::Error::custom)?;
+                        claim.set(claim_value(access.next_value::<Int>()?)?);
+                    }
+                    None => break,
+                }
+            } else if self.is_human_readable {
+                match access.next_entry::<&str, Int>()? {
                     Some((k, val)) => match tv.mut_by_name(k).map_err(de::Error::custom) {
-                        Ok(claim) => claim.set(val),
+                        Ok(claim) => claim.set(claim_value(val)?),
                         Err(e) => return Err(e),
                     },
                     None => break,
                 }
             } else {
                 // !is_human_readable
-                match access.next_entry::<i32, i8>()? {
+                match access.next_entry::<i32, Int>()? {
                     Some((k, val)) => match tv.mut_by_key(k).map_err(de::Error::custom) {
-                        Ok(claim) => claim.set(val),
+                        Ok(claim) => claim.set(claim_value(val)?),
                         Err(e) => return Err(e),
                     },
                     None => break,
@@ -234,6 +475,31 @@ impl<'de> Visitor<'de> for TrustVectorVisitor {
     }
 }
 
+// Look up a claim by either its name or its key (see LenientTrustVector)
+fn mut_by_map_key<'a>(tv: &'a mut TrustVector, k: &MapKey) -> Result<&'a mut TrustClaim, Error> {
+    match k {
+        MapKey::Text(name) => match name.parse::<i32>() {
+            Ok(key) => tv.mut_by_key(key),
+            Err(_) => tv.mut_by_name(name),
+        },
+        MapKey::Integer(key) => match i32::try_from(*key) {
+            Ok(key) => tv.mut_by_key(key),
+            Err(_) => Err(Error::ParseError(format!(
+                "claim key {key} is out of range"
+            ))),
+        },
+    }
+}
+
+fn claim_value<E: de::Error>(val: Int) -> Result<i8, E> {
+    i8::try_from(val.0).map_err(|_| {
+        E::invalid_value(
+            de::Unexpected::Signed(val.0),
+            &"a claim value between -128 and 127",
+        )
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -258,6 +524,183 @@ mod test {
         }
     }
 
+    #[test]
+    fn set_all_to_tier() {
+        let mut tv = TrustVector::new();
+
+        for tier in [
+            TrustTier::Affirming,
+            TrustTier::Warning,
+            TrustTier::Contraindicated,
+            TrustTier::None,
+        ] {
+            tv.set_all_to_tier(tier);
+            assert!(tv.iter().all(|c| c.tier() == tier));
+        }
+
+        // unlike clear(), TrustTier::None sets the claims (to no_claim)
+        assert!(tv.any_set());
+    }
+
+    #[test]
+    fn lenient_mixed_keys() {
+        let mut expected = TrustVector::new();
+        expected.instance_identity.set(2);
+        expected.hardware.set(32);
+        expected.sourced_data.set(96);
+
+        let json = r#"{"instance-identity": 2, "4": 32, "sourced_data": 96}"#;
+        let tv: LenientTrustVector = serde_json::from_str(json).unwrap();
+        assert_eq!(tv.0, expected);
+
+        // plain decoding only accepts names in JSON
+        let res: Result<TrustVector, _> = serde_json::from_str(json);
+        assert!(res.is_err());
+
+        // {0: 2, "hardware": 32, 7: 96}
+        let cbor = [
+            0xa3, 0x00, 0x02, 0x68, b'h', b'a', b'r', b'd', b'w', b'a', b'r', b'e', 0x18, 0x20,
+            0x07, 0x18, 0x60,
+        ];
+        let tv: LenientTrustVector = from_reader(cbor.as_slice()).unwrap();
+        assert_eq!(tv.0, expected);
+
+        // plain decoding only accepts keys in CBOR
+        let res: Result<TrustVector, _> = from_reader(cbor.as_slice());
+        assert!(res.is_err());
+
+        let res: Result<LenientTrustVector, _> = serde_json::from_str(r#"{"0": 2, "8": 2}"#);
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "invalid key: 8 at line 1 column 12"
+        );
+    }
+
+    #[test]
+    fn array() {
+        let mut tv = TrustVector::new();
+        assert_eq!(tv.to_array(), [None; 8]);
+
+        tv.configuration.set(2);
+        tv.hardware.set(-1);
+        tv.sourced_data.set(96);
+
+        let arr = tv.to_array();
+        assert_eq!(
+            arr,
+            [None, Some(2), None, None, Some(-1), None, None, Some(96)]
+        );
+        assert_eq!(TrustVector::from_array(arr), tv);
+    }
+
+    #[test]
+    fn by_name() {
+        let mut tv = TrustVector::new();
+        tv.mut_by_name("runtime_opaque")
+            .unwrap()
+            .set(ENCRYPTED_MEMORY_RUNTIME);
+        assert_eq!(tv.runtime_opaque, ENCRYPTED_MEMORY_RUNTIME);
+        assert_eq!(
+            tv.by_name("runtime-opaque").unwrap(),
+            tv.by_name("runtime_opaque").unwrap()
+        );
+
+        assert!(
+            matches!(tv.by_name("runtime opaque"), Err(Error::InvalidName(n))
+                if n == "runtime opaque")
+        );
+
+        let tv1: TrustVector = serde_json::from_str(r#"{"runtime_opaque":2}"#).unwrap();
+        let tv2: TrustVector = serde_json::from_str(r#"{"runtime-opaque":2}"#).unwrap();
+        assert_eq!(tv1, tv2);
+        assert_eq!(tv1.runtime_opaque, ENCRYPTED_MEMORY_RUNTIME);
+
+        let val = serde_json::to_string(&tv1).unwrap();
+        assert_eq!(val, r#"{"runtime-opaque":2}"#);
+    }
+
+    #[test]
+    fn clear() {
+        let mut tv = TrustVector::new();
+        tv.instance_identity.set(TRUSTWORTHY_INSTANCE);
+        tv.hardware.set(GENUINE_HARDWARE);
+        tv.sourced_data.set(TRUSTED_SOURCES);
+        assert!(tv.any_set());
+
+        tv.unset_by_name("hardware").unwrap();
+        assert!(!tv.hardware.is_set());
+
+        tv.unset_by_key(7).unwrap();
+        assert!(!tv.sourced_data.is_set());
+        assert!(tv.instance_identity.is_set());
+
+        assert!(matches!(tv.unset_by_name("foo"), Err(Error::InvalidName(n)) if n == "foo"));
+        assert!(matches!(tv.unset_by_key(8), Err(Error::InvalidKey(8))));
+
+        tv.set_all(NO_CLAIM);
+        assert!(tv.any_set());
+
+        tv.clear();
+        assert!(!tv.any_set());
+    }
+
+    #[test]
+    fn set_from_expr() {
+        let mut tv = TrustVector::new();
+        tv.set_from_expr("configuration=approved_config, executables=unrecognized_rt")
+            .unwrap();
+        assert_eq!(tv.configuration, APPROVED_CONFIG);
+        assert_eq!(tv.executables, UNRECOGNIZED_RUNTIME);
+        assert!(!tv.hardware.is_set());
+
+        tv.set_from_expr("").unwrap();
+        assert_eq!(tv.configuration, APPROVED_CONFIG);
+
+        let res = tv.set_from_expr("hardware=genuine_hw, foo=approved_rt, executables=bar");
+        assert!(matches!(res, Err(Error::ParseError(t))
+                if t == "at 21: unknown claim name \"foo\"; \
+                         at 38: unknown value tag \"bar\" for claim \"executables\""));
+        assert!(!tv.hardware.is_set());
+        assert_eq!(tv.executables, UNRECOGNIZED_RUNTIME);
+
+        let res = tv.set_from_expr("hardware");
+        assert!(matches!(res, Err(Error::ParseError(t))
+                if t == "at 0: expected name=tag, but found \"hardware\""));
+    }
+
+    #[test]
+    fn from_pairs() {
+        let tv = TrustVector::from_pairs(&[(1, 2), (4, 96)]).unwrap();
+        assert_eq!(tv.configuration.value(), 2);
+        assert_eq!(tv.hardware.value(), 96);
+        assert!(!tv.executables.is_set());
+
+        let tv2 = TrustVector::from_named_pairs(&[("configuration", 2), ("hardware", 96)]).unwrap();
+        assert_eq!(tv, tv2);
+
+        let res = TrustVector::from_pairs(&[(1, 2), (8, 0)]);
+        assert!(matches!(res, Err(Error::InvalidKey(8))));
+
+        let res = TrustVector::from_named_pairs(&[("foo", 0)]);
+        assert!(matches!(res, Err(Error::InvalidName(t)) if t == "foo"));
+    }
+
+    #[test]
+    fn set_from_colors() {
+        let mut tv = TrustVector::new();
+        tv.set_from_colors(&[("configuration", Color::Green), ("hardware", Color::Red)])
+            .unwrap();
+        assert_eq!(tv.configuration.value(), 2);
+        assert_eq!(tv.configuration.tier(), TrustTier::Affirming);
+        assert_eq!(tv.hardware.value(), 96);
+        assert_eq!(tv.hardware.tier(), TrustTier::Contraindicated);
+        assert!(!tv.executables.is_set());
+
+        let res = tv.set_from_colors(&[("executables", Color::Amber), ("foo", Color::Red)]);
+        assert!(matches!(res, Err(Error::InvalidName(t)) if t == "foo"));
+        assert!(!tv.executables.is_set());
+    }
+
     #[test]
     fn serde() {
         let mut tv = TrustVector::new();
@@ -291,5 +734,13 @@ mod test {
 
         let tv2: TrustVector = from_reader(buf.as_slice()).unwrap();
         assert_eq!(tv, tv2);
+
+        let tv2: TrustVector = serde_json::from_str(r#"{"hardware":2.0}"#).unwrap();
+        assert_eq!(tv2.hardware, GENUINE_HARDWARE);
+
+        let err = serde_json::from_str::<TrustVector>(r#"{"hardware":200}"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid value: integer `200`, expected a claim value"));
     }
 }
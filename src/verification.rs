@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::algorithm::Algorithm;
+use crate::format::Format;
+
+/// Key material used to verify a signed EAR, along with the algorithm it is to be used with
+///
+/// See [`SigningKey`](crate::SigningKey) for the private keys used to sign an EAR.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerificationKey {
+    /// A JWK-encoded public key
+    Jwk { alg: Algorithm, key: Vec<u8> },
+    /// A PEM-encoded public key
+    Pem { alg: Algorithm, key: Vec<u8> },
+    /// A DER-encoded (SubjectPublicKeyInfo) public key
+    Der { alg: Algorithm, key: Vec<u8> },
+}
+
+impl VerificationKey {
+    /// Create a new verification key from a JWK-encoded public key
+    pub fn jwk(alg: Algorithm, key: &[u8]) -> VerificationKey {
+        VerificationKey::Jwk {
+            alg,
+            key: key.to_vec(),
+        }
+    }
+
+    /// Create a new verification key from a PEM-encoded public key
+    pub fn pem(alg: Algorithm, key: &[u8]) -> VerificationKey {
+        VerificationKey::Pem {
+            alg,
+            key: key.to_vec(),
+        }
+    }
+
+    /// Create a new verification key from a DER-encoded (SubjectPublicKeyInfo) public key
+    pub fn der(alg: Algorithm, key: &[u8]) -> VerificationKey {
+        VerificationKey::Der {
+            alg,
+            key: key.to_vec(),
+        }
+    }
+
+    /// Return the algorithm the key is to be used with
+    pub fn alg(&self) -> Algorithm {
+        match self {
+            VerificationKey::Jwk { alg, .. }
+            | VerificationKey::Pem { alg, .. }
+            | VerificationKey::Der { alg, .. } => *alg,
+        }
+    }
+}
+
+/// Details of how a signed EAR was verified, for audit logging (see e.g.
+/// [`Ear::from_jwt_jwk_verbose`](crate::Ear::from_jwt_jwk_verbose))
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyMeta {
+    /// The algorithm with which the signature was verified
+    pub algorithm: Algorithm,
+    /// The identifier of the signing key, from the token's `kid` header (if present)
+    ///
+    /// A COSE key identifier is a byte string: it is converted to a string if it is valid UTF-8,
+    /// and is otherwise hex-encoded.
+    pub kid: Option<String>,
+    /// The format of the token
+    pub format: Format,
+}